@@ -0,0 +1,253 @@
+//! Interpolation of colors and lengths over time, used by the components to
+//! fade between states.
+//!
+//! Everything here jumps to its target right away while reduced motion is
+//! enabled with [`set_reduced_motion`].
+
+use std::time::{Duration, Instant};
+
+use gpui::*;
+
+/// Duration of state fades, like hovering a button.
+pub const FADE_DURATION: Duration = Duration::from_millis(120);
+
+/// Duration of the cross-fade between themes.
+pub const THEME_FADE_DURATION: Duration = Duration::from_millis(250);
+
+#[derive(Default)]
+struct Motion {
+    reduced: bool,
+}
+
+impl Global for Motion {}
+
+/// Turns off animations, for users who prefer reduced motion.
+pub fn set_reduced_motion(reduced: bool, cx: &mut App) {
+    cx.default_global::<Motion>().reduced = reduced;
+}
+
+pub fn reduced_motion(cx: &App) -> bool {
+    cx.try_global::<Motion>()
+        .is_some_and(|motion| motion.reduced)
+}
+
+/// Values that can be blended, `t` going from 0 for `self` to 1 for `to`.
+pub trait Lerp {
+    fn lerp(&self, to: &Self, t: f32) -> Self;
+}
+
+impl Lerp for f32 {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        self + (to - self) * t
+    }
+}
+
+impl Lerp for Pixels {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        px(f32::from(*self).lerp(&f32::from(*to), t))
+    }
+}
+
+/// Blends in RGB, which doesn't pass through unrelated hues on the way. A
+/// fully transparent end takes the color of the other one, so fading in
+/// from nothing doesn't darken.
+impl Lerp for Hsla {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        let (mut from, mut to) = (self.to_rgb(), to.to_rgb());
+        if from.a == 0.0 {
+            from = Rgba { a: 0.0, ..to };
+        } else if to.a == 0.0 {
+            to = Rgba { a: 0.0, ..from };
+        }
+        Rgba {
+            r: from.r.lerp(&to.r, t),
+            g: from.g.lerp(&to.g, t),
+            b: from.b.lerp(&to.b, t),
+            a: from.a.lerp(&to.a, t),
+        }
+        .into()
+    }
+}
+
+impl<T: Lerp + Clone> Lerp for Option<T> {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        match (self, to) {
+            (Some(from), Some(to)) => Some(from.lerp(to, t)),
+            _ => to.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    #[default]
+    EaseOut,
+    EaseInOut,
+}
+
+impl Easing {
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => t,
+            Self::EaseIn => t * t * t,
+            Self::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Self::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+        }
+    }
+}
+
+/// A value moving towards its target over time.
+#[derive(Debug, Clone)]
+pub struct Transition<T> {
+    from: T,
+    to: T,
+    started: Instant,
+    duration: Duration,
+    easing: Easing,
+}
+
+impl<T: Lerp + Clone + PartialEq> Transition<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            from: value.clone(),
+            to: value,
+            started: Instant::now(),
+            duration: FADE_DURATION,
+            easing: Easing::default(),
+        }
+    }
+
+    pub fn duration(self, duration: Duration) -> Self {
+        Self { duration, ..self }
+    }
+
+    pub fn easing(self, easing: Easing) -> Self {
+        Self { easing, ..self }
+    }
+
+    pub fn target(&self) -> &T {
+        &self.to
+    }
+
+    /// Starts moving from the current value towards `target`, unless it
+    /// already is the target. Jumps there with reduced motion.
+    pub fn set_target(&mut self, target: T, cx: &App) {
+        if self.to == target {
+            return;
+        }
+        self.from = if reduced_motion(cx) {
+            target.clone()
+        } else {
+            self.value()
+        };
+        self.to = target;
+        self.started = Instant::now();
+    }
+
+    fn progress(&self) -> f32 {
+        if self.duration.is_zero() {
+            return 1.0;
+        }
+        self.started.elapsed().as_secs_f32() / self.duration.as_secs_f32()
+    }
+
+    pub fn is_animating(&self) -> bool {
+        self.from != self.to && self.progress() < 1.0
+    }
+
+    pub fn value(&self) -> T {
+        if !self.is_animating() {
+            return self.to.clone();
+        }
+        self.from.lerp(&self.to, self.easing.apply(self.progress()))
+    }
+}
+
+/// Fades of the hover and press state of an element, from 0 to 1.
+///
+/// Get it with [`Interaction::use_state`] while rendering and add
+/// [`Interaction::tracker`] as a child of the element, which keeps the
+/// state up to date and requests frames while it fades.
+pub struct Interaction {
+    hover: Transition<f32>,
+    press: Transition<f32>,
+}
+
+impl Interaction {
+    pub fn use_state(id: impl Into<ElementId>, window: &mut Window, cx: &mut App) -> Entity<Self> {
+        window.use_keyed_state(id, cx, |_, _| Self {
+            hover: Transition::new(0.0),
+            press: Transition::new(0.0),
+        })
+    }
+
+    pub fn hover(&self) -> f32 {
+        self.hover.value()
+    }
+
+    pub fn press(&self) -> f32 {
+        self.press.value()
+    }
+
+    /// `base` faded towards `hover` and then `active` as far as the element
+    /// is hovered and pressed.
+    pub fn color(&self, base: Hsla, hover: Hsla, active: Hsla) -> Hsla {
+        base.lerp(&hover, self.hover()).lerp(&active, self.press())
+    }
+
+    /// An invisible element covering its parent which tracks the pointer and
+    /// redraws the view it is in when the hover or press state changes.
+    pub fn tracker(state: Entity<Self>) -> impl IntoElement {
+        canvas(
+            |bounds, window, _| window.insert_hitbox(bounds, HitboxBehavior::Normal),
+            move |_, hitbox, window, cx| {
+                let view = window.current_view();
+                let hovered = hitbox.is_hovered(window);
+                let animating = state.update(cx, |this, cx| {
+                    this.hover.set_target(if hovered { 1.0 } else { 0.0 }, cx);
+                    if !hovered {
+                        this.press.set_target(0.0, cx);
+                    }
+                    this.hover.is_animating() || this.press.is_animating()
+                });
+                if animating {
+                    window.request_animation_frame();
+                }
+
+                let hover_hitbox = hitbox.clone();
+                window.on_mouse_event(move |_: &MouseMoveEvent, phase, window, cx| {
+                    if phase.bubble() && hover_hitbox.is_hovered(window) != hovered {
+                        cx.notify(view);
+                    }
+                });
+                let press_state = state.clone();
+                window.on_mouse_event(move |event: &MouseDownEvent, phase, window, cx| {
+                    if phase.bubble()
+                        && event.button == MouseButton::Left
+                        && hitbox.is_hovered(window)
+                    {
+                        press_state.update(cx, |this, cx| this.press.set_target(1.0, cx));
+                        cx.notify(view);
+                    }
+                });
+                window.on_mouse_event(move |_: &MouseUpEvent, phase, _, cx| {
+                    if phase.bubble() && *state.read(cx).press.target() != 0.0 {
+                        state.update(cx, |this, cx| this.press.set_target(0.0, cx));
+                        cx.notify(view);
+                    }
+                });
+            },
+        )
+        .absolute()
+        .size_full()
+    }
+}
@@ -3,6 +3,7 @@ use gpui::{prelude::*, *};
 
 #[derive(IntoElement)]
 pub struct Button {
+    id: ElementId,
     base: Stateful<Div>,
     hover_color: Option<Hsla>,
     base_color: Option<Hsla>,
@@ -27,7 +28,9 @@ impl Button {
     });
 
     pub fn new(id: impl Into<ElementId>) -> Self {
+        let id = id.into();
         Self {
+            id: id.clone(),
             base: v_flex().id(id),
             base_color: None,
             hover_color: None,
@@ -79,13 +82,36 @@ impl InteractiveElement for Button {
 impl StatefulInteractiveElement for Button {}
 
 impl RenderOnce for Button {
-    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
-        let hover_color = self.hover_color.unwrap_or(cx.theme().foreground);
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let style = cx.theme().button();
+        let hover_color = self.hover_color.unwrap_or(style.hover);
+        let active_color = self.hover_color.unwrap_or(style.active);
+        let interaction = Interaction::use_state(self.id, window, cx);
+        // Only set when asked for or while fading, leaving backgrounds set on
+        // the button itself in place.
+        let base_color = self.base_color.or(cx.theme().components.button.background);
+        let bg = {
+            let interaction = interaction.read(cx);
+            if interaction.hover() > 0.0 || interaction.press() > 0.0 {
+                Some(interaction.color(
+                    base_color.unwrap_or(transparent_black()),
+                    hover_color,
+                    active_color,
+                ))
+            } else {
+                base_color
+            }
+        };
+        // No `relative()`, the tracker is placed in the button either way
+        // and buttons may be absolutely positioned themselves.
         self.base
-            .hover(|style| style.cursor_pointer().bg(hover_color))
-            .when_some(self.base_color, |this, bg| this.bg(bg))
+            .hover(|this| this.cursor_pointer())
+            .when_some(bg, |this, bg| this.bg(bg))
+            .child(Interaction::tracker(interaction))
             .when(self.bordered, |this| {
-                this.border_1().border_color(cx.theme().border).rounded_lg()
+                this.border_1()
+                    .border_color(style.border)
+                    .rounded(style.radius)
             })
     }
 }
@@ -3,6 +3,7 @@ use gpui::{prelude::*, *};
 
 #[derive(IntoElement)]
 pub struct Checkbox {
+    id: ElementId,
     base: Stateful<Div>,
     selected: bool,
     label: Option<AnyElement>,
@@ -10,7 +11,9 @@ pub struct Checkbox {
 
 impl Checkbox {
     pub fn new(id: impl Into<ElementId>, selected: bool) -> Self {
+        let id = id.into();
         Self {
+            id: id.clone(),
             base: h_flex().id(id),
             selected,
             label: None,
@@ -32,7 +35,19 @@ impl InteractiveElement for Checkbox {
 }
 
 impl RenderOnce for Checkbox {
-    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        // Fades the check mark in and out.
+        let check = window.use_keyed_state(self.id, cx, |_, _| {
+            Transition::new(if self.selected { 1.0 } else { 0.0 })
+        });
+        let opacity = check.update(cx, |check, cx| {
+            check.set_target(if self.selected { 1.0 } else { 0.0 }, cx);
+            if check.is_animating() {
+                window.request_animation_frame();
+            }
+            check.value()
+        });
+
         self.base
             .items_center()
             .gap_1()
@@ -42,10 +57,11 @@ impl RenderOnce for Checkbox {
                     .size_5()
                     .border_1()
                     .border_color(cx.theme().border)
-                    .when(self.selected, |this| {
+                    .when(opacity > 0.0, |this| {
                         this.child(
                             svg()
-                                .text_color(gpui::white())
+                                .opacity(opacity)
+                                .text_color(cx.theme().text)
                                 .size_full()
                                 .path("checkbox-inner"),
                         )
@@ -0,0 +1,122 @@
+use std::ops::Range;
+
+use gpui::*;
+
+/// Bonus for a match directly following the previous one.
+const CONSECUTIVE_BONUS: i64 = 5;
+/// Bonus for a match at the start of a word or a camel case hump.
+const WORD_START_BONUS: i64 = 8;
+/// Upper bound of the penalty for characters skipped between two matches.
+const MAX_GAP_PENALTY: i64 = 3;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    pub score: i64,
+    /// Byte offsets of the matched characters in the candidate.
+    pub positions: Vec<usize>,
+}
+
+fn fold(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// Matches the characters of `query` in order and case insensitively against
+/// `candidate`. Whitespace in the query is ignored. Returns `None` if not all
+/// query characters could be matched.
+pub fn fuzzy_match(query: &str, candidate: &str) -> Option<FuzzyMatch> {
+    let mut query = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(fold)
+        .peekable();
+    let mut result = FuzzyMatch {
+        score: 0,
+        positions: Vec::new(),
+    };
+    let mut previous: Option<char> = None;
+    let mut last_match: Option<usize> = None;
+
+    for (char_ix, (byte_ix, c)) in candidate.char_indices().enumerate() {
+        let Some(&q) = query.peek() else {
+            break;
+        };
+        if fold(c) == q {
+            let word_start = previous.is_none_or(|previous| {
+                !previous.is_alphanumeric() || (previous.is_lowercase() && c.is_uppercase())
+            });
+            result.score += 1;
+            if word_start {
+                result.score += WORD_START_BONUS;
+            }
+            match last_match {
+                Some(last) if last + 1 == char_ix => result.score += CONSECUTIVE_BONUS,
+                Some(last) => result.score -= ((char_ix - last - 1) as i64).min(MAX_GAP_PENALTY),
+                None => {}
+            }
+            result.positions.push(byte_ix);
+            last_match = Some(char_ix);
+            query.next();
+        }
+        previous = Some(c);
+    }
+
+    query.peek().is_none().then_some(result)
+}
+
+/// Merges the matched byte `positions` of `text` into ranges of whole
+/// characters.
+pub fn match_ranges(text: &str, positions: &[usize]) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    for &position in positions {
+        let len = text[position..].chars().next().map_or(0, char::len_utf8);
+        match ranges.last_mut() {
+            Some(range) if range.end == position => range.end = position + len,
+            _ => ranges.push(position..position + len),
+        }
+    }
+    ranges
+}
+
+/// Renders `text` with the matched `positions` highlighted in `color`.
+pub fn highlighted_text(text: SharedString, positions: &[usize], color: Hsla) -> StyledText {
+    let style = HighlightStyle {
+        color: Some(color),
+        font_weight: Some(FontWeight::BOLD),
+        ..Default::default()
+    };
+    let highlights = match_ranges(&text, positions)
+        .into_iter()
+        .map(|range| (range, style))
+        .collect::<Vec<_>>();
+    StyledText::new(text).with_highlights(highlights)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn matches_subsequence() {
+        let m = fuzzy_match("opw", "Open With").unwrap();
+        assert_eq!(m.positions, vec![0, 1, 5]);
+        assert!(fuzzy_match("xyz", "Open With").is_none());
+        assert_eq!(fuzzy_match("", "Open").unwrap().positions, Vec::<usize>::new());
+    }
+
+    #[test]
+    fn prefers_word_starts_and_runs() {
+        let word_start = fuzzy_match("sb", "Sort By").unwrap();
+        let scattered = fuzzy_match("sb", "Subscribe").unwrap();
+        assert!(word_start.score > scattered.score);
+
+        let run = fuzzy_match("ins", "Insert").unwrap();
+        let gaps = fuzzy_match("ins", "Image Nodes").unwrap();
+        assert!(run.score > gaps.score);
+    }
+
+    #[test]
+    fn merges_ranges() {
+        assert_eq!(match_ranges("Insert", &[0, 1, 2, 4]), vec![0..3, 4..5]);
+        assert_eq!(match_ranges("äb", &[0, 2]), vec![0..3]);
+    }
+}
@@ -491,7 +491,7 @@ impl Element for TextElement {
         let style = window.text_style();
 
         let (display_text, text_color) = if content.is_empty() {
-            (input.placeholder.clone(), cx.theme().selection)
+            (input.placeholder.clone(), cx.theme().placeholder)
         } else {
             (content.clone(), style.color)
         };
@@ -567,7 +567,7 @@ impl Element for TextElement {
                         point(bounds.left() + cursor_pos, bounds.top() + inset),
                         size(px(1.), bounds.bottom() - bounds.top() - inset * 2),
                     ),
-                    cx.theme().text,
+                    cx.theme().input().text,
                 )),
             )
         } else {
@@ -584,7 +584,7 @@ impl Element for TextElement {
                             bounds.bottom() - inset,
                         ),
                     ),
-                    cx.theme().selection,
+                    cx.theme().input().active,
                 )),
                 None,
             )
@@ -79,8 +79,8 @@ impl RenderOnce for Label {
             //.justify_center()
             .map(|this| match self.x_align {
                 TextAlignment::Center => this.justify_center(),
-                TextAlignment::Start => this.px_2().justify_start(),
-                TextAlignment::End => this.px_2().justify_end(),
+                TextAlignment::Start => this.px(cx.theme().spacing.md).justify_start(),
+                TextAlignment::End => this.px(cx.theme().spacing.md).justify_end(),
             })
             .map(|this| match self.y_align {
                 TextAlignment::Center => this.items_center(),
@@ -88,7 +88,9 @@ impl RenderOnce for Label {
                 TextAlignment::End => this.items_end(),
             })
             .when(self.border, |this| {
-                this.border_1().border_color(cx.theme().border).rounded_sm()
+                this.border_1()
+                    .border_color(cx.theme().border)
+                    .rounded(cx.theme().radii.sm)
             })
             .child(self.text)
     }
@@ -53,17 +53,28 @@ impl Divider {
 
 impl RenderOnce for Divider {
     fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        // The inset is applied as padding, so that the line still fits the
+        // full length of its parent.
+        let inset = match self.inset {
+            Some(Length::Definite(inset)) => Some(inset),
+            _ => None,
+        };
         div()
+            .flex_none()
             .map(|this| match self.layout {
                 Layout::Vertical => this
                     .w(self.thickness)
                     .h_full()
-                    .when_some(self.inset, |this, _| this.my_1p5() /*.my(inset)*/),
+                    .when_some(inset, |this, inset| this.py(inset)),
                 Layout::Horizontal => this
                     .h(self.thickness)
                     .w_full()
-                    .when_some(self.inset, |this, _| this.mx_1p5()),
+                    .when_some(inset, |this, inset| this.px(inset)),
             })
-            .bg(self.color.unwrap_or(cx.theme().border))
+            .child(
+                div()
+                    .size_full()
+                    .bg(self.color.unwrap_or(cx.theme().border)),
+            )
     }
 }
@@ -0,0 +1,687 @@
+use std::{fs, io, path::Path};
+
+use crate::prelude::*;
+use gpui::{prelude::*, *};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("failed to parse dock layout: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("io: {0}")]
+    Io(#[from] io::Error),
+}
+
+type Result<T> = std::result::Result<T, Error>;
+
+/// Width of the drop targets shown at the window edges while dragging a
+/// panel, and of the strips of collapsed docks.
+const EDGE_SIZE: Pixels = px(32.0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DockZone {
+    Left,
+    Center,
+    Right,
+    Bottom,
+}
+
+impl DockZone {
+    const ALL: [Self; 4] = [Self::Left, Self::Center, Self::Right, Self::Bottom];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// A view hosted by a [`DockLayout`]. The key identifies the panel in the
+/// serialized [`DockState`].
+#[derive(Clone)]
+pub struct DockPanel {
+    key: SharedString,
+    title: SharedString,
+    view: AnyView,
+}
+
+impl DockPanel {
+    pub fn new(
+        key: impl Into<SharedString>,
+        title: impl Into<SharedString>,
+        view: impl Into<AnyView>,
+    ) -> Self {
+        Self {
+            key: key.into(),
+            title: title.into(),
+            view: view.into(),
+        }
+    }
+
+    pub fn key(&self) -> &SharedString {
+        &self.key
+    }
+}
+
+#[derive(Clone)]
+struct DraggedPanel {
+    key: SharedString,
+    title: SharedString,
+}
+
+impl Render for DraggedPanel {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .px(cx.theme().spacing.md)
+            .py_1()
+            .bg(cx.theme().selection)
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded(cx.theme().radii.lg)
+            .text_color(cx.theme().text)
+            .child(self.title.clone())
+    }
+}
+
+/// Serializable arrangement of a [`DockLayout`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DockState {
+    pub zones: Vec<DockZoneState>,
+    /// Sizes of the left, center and right docks.
+    pub horizontal: SplitLayout,
+    /// Sizes of the upper area and the bottom dock.
+    pub vertical: SplitLayout,
+    /// Keys of the panels shown in their own windows.
+    pub detached: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DockZoneState {
+    pub zone: DockZone,
+    pub panels: Vec<String>,
+    pub active: usize,
+    pub collapsed: bool,
+}
+
+impl DockState {
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_json(&fs::read_to_string(path)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+}
+
+/// A stack of panels shown as tabs in one zone of a [`DockLayout`].
+pub struct DockArea {
+    zone: DockZone,
+    dock: WeakEntity<DockLayout>,
+    focus: FocusHandle,
+    panels: Vec<DockPanel>,
+    active: usize,
+    collapsed: bool,
+}
+
+impl DockArea {
+    fn new(zone: DockZone, dock: WeakEntity<DockLayout>, cx: &mut Context<Self>) -> Self {
+        Self {
+            zone,
+            dock,
+            focus: cx.focus_handle(),
+            panels: Vec::new(),
+            active: 0,
+            collapsed: false,
+        }
+    }
+
+    fn insert(&mut self, panel: DockPanel, ix: Option<usize>, cx: &mut Context<Self>) {
+        let ix = ix.unwrap_or(self.panels.len()).min(self.panels.len());
+        self.panels.insert(ix, panel);
+        self.active = ix;
+        cx.notify();
+    }
+
+    fn remove(&mut self, key: &str, cx: &mut Context<Self>) -> Option<(usize, DockPanel)> {
+        let ix = self.panels.iter().position(|panel| panel.key == key)?;
+        let panel = self.panels.remove(ix);
+        if self.active > ix || self.active >= self.panels.len() {
+            self.active = self.active.saturating_sub(1);
+        }
+        cx.notify();
+        Some((ix, panel))
+    }
+
+    /// Runs `f` on the dock once the current update finished, as it updates
+    /// this area as well.
+    fn update_dock(
+        &self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        f: impl FnOnce(&mut DockLayout, &mut Window, &mut Context<DockLayout>) + 'static,
+    ) {
+        let dock = self.dock.clone();
+        window.defer(cx, move |window, cx| {
+            _ = dock.update(cx, |dock, cx| f(dock, window, cx));
+        });
+    }
+
+    fn render_drop_target(&self, ix: Option<usize>, cx: &mut Context<Self>) -> impl IntoElement {
+        let zone = self.zone;
+        let selection = cx.theme().selection;
+        div()
+            .id("dock-drop-target")
+            .absolute()
+            .inset_0()
+            .drag_over::<DraggedPanel>(move |style, _, _, _| style.bg(selection.opacity(0.35)))
+            .on_drop(cx.listener(move |this, panel: &DraggedPanel, window, cx| {
+                let key = panel.key.clone();
+                this.update_dock(window, cx, move |dock, _, cx| {
+                    dock.move_panel(&key, zone, ix, cx)
+                });
+            }))
+    }
+}
+
+impl Render for DockArea {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let zone = self.zone;
+        let selection = cx.theme().selection;
+        let dragging = cx.has_active_drag();
+        let collapse_icon = match zone {
+            DockZone::Left => Some("chevron_left"),
+            DockZone::Right => Some("chevron_right"),
+            DockZone::Bottom => Some("chevron_down"),
+            DockZone::Center => None,
+        };
+
+        let tabs = self.panels.iter().enumerate().map(|(ix, panel)| {
+            let dragged = DraggedPanel {
+                key: panel.key.clone(),
+                title: panel.title.clone(),
+            };
+            Tab::new(("dock-tab", ix), panel.title.clone())
+                .selected(ix == self.active)
+                .on_click(cx.listener(move |this, _, _, cx| {
+                    this.active = ix;
+                    cx.notify();
+                }))
+                .on_drag(dragged, |panel, _, _, cx| cx.new(|_| panel.clone()))
+                // Dropping onto a tab inserts the panel in front of it.
+                .drag_over::<DraggedPanel>(move |style, _, _, _| {
+                    style.border_l_2().border_color(selection)
+                })
+                .on_drop(cx.listener(move |this, panel: &DraggedPanel, window, cx| {
+                    let key = panel.key.clone();
+                    this.update_dock(window, cx, move |dock, _, cx| {
+                        dock.move_panel(&key, zone, Some(ix), cx)
+                    });
+                }))
+        });
+
+        let active = self.panels.get(self.active).cloned();
+
+        v_flex()
+            .size_full()
+            .bg(cx.theme().background)
+            .child(
+                TabBar::new(("dock-tabs", zone.index()), self.focus.clone())
+                    .reorderable(false)
+                    .tabs(tabs)
+                    .child(div().flex_1())
+                    .when_some(active.clone(), |this, panel| {
+                        this.child(
+                            Button::new("dock-detach")
+                                .p_1()
+                                .rounded(cx.theme().radii.sm)
+                                .hover(selection)
+                                .child(svg().size_4().text_color(cx.theme().text).path("arrow_up"))
+                                .on_click(cx.listener(move |this, _, window, cx| {
+                                    let key = panel.key.clone();
+                                    this.update_dock(window, cx, move |dock, _, cx| {
+                                        dock.detach(&key, cx)
+                                    });
+                                })),
+                        )
+                    })
+                    .when_some(collapse_icon, |this, icon| {
+                        this.child(
+                            Button::new("dock-collapse")
+                                .p_1()
+                                .rounded(cx.theme().radii.sm)
+                                .hover(selection)
+                                .child(svg().size_4().text_color(cx.theme().text).path(icon))
+                                .on_click(cx.listener(move |this, _, window, cx| {
+                                    this.update_dock(window, cx, move |dock, _, cx| {
+                                        dock.set_collapsed(zone, true, cx)
+                                    });
+                                })),
+                        )
+                    }),
+            )
+            .child(
+                div()
+                    .id("dock-content")
+                    .relative()
+                    .flex_1()
+                    .overflow_hidden()
+                    .when_some(active, |this, panel| this.child(panel.view))
+                    .when(dragging, |this| {
+                        this.child(self.render_drop_target(None, cx))
+                    }),
+            )
+    }
+}
+
+/// Window showing a single panel taken out of a [`DockLayout`].
+struct DetachedPanel {
+    dock: WeakEntity<DockLayout>,
+    panel: DockPanel,
+}
+
+impl Render for DetachedPanel {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let key = self.panel.key.clone();
+        let dock = self.dock.clone();
+        v_flex()
+            .size_full()
+            .bg(cx.theme().background)
+            .text_color(cx.theme().text)
+            .child(
+                h_flex()
+                    .h(cx.theme().sizes.bar_height)
+                    .px(cx.theme().spacing.md)
+                    .justify_between()
+                    .items_center()
+                    .bg(cx.theme().foreground)
+                    .child(self.panel.title.clone())
+                    .child(
+                        Button::new("dock-attach")
+                            .px(cx.theme().spacing.md)
+                            .rounded(cx.theme().radii.sm)
+                            .hover(cx.theme().selection)
+                            .child("Dock")
+                            .on_click(move |_, window, cx| {
+                                _ = dock.update(cx, |dock, cx| dock.attach(&key, cx));
+                                window.remove_window();
+                            }),
+                    ),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .overflow_hidden()
+                    .child(self.panel.view.clone()),
+            )
+    }
+}
+
+struct Detached {
+    panel: DockPanel,
+    zone: DockZone,
+    window: WindowHandle<DetachedPanel>,
+}
+
+/// IDE style arrangement of panels in a center area surrounded by left,
+/// right and bottom docks. Panels can be dragged between the docks, stacked
+/// as tabs, moved into their own window and docks collapsed to the edge.
+pub struct DockLayout {
+    areas: [Entity<DockArea>; 4],
+    /// Splits the left, center and right docks.
+    horizontal: Entity<SplitPane>,
+    /// Splits the upper area and the bottom dock.
+    vertical: Entity<SplitPane>,
+    detached: Vec<Detached>,
+}
+
+impl DockLayout {
+    pub fn new(cx: &mut Context<Self>) -> Self {
+        let dock = cx.weak_entity();
+        let areas = DockZone::ALL.map(|zone| cx.new(|cx| DockArea::new(zone, dock.clone(), cx)));
+        let [left, center, right, bottom] = areas.clone();
+        let horizontal = cx.new(|cx| {
+            SplitPane::horizontal(
+                [
+                    Pane::new(left).size(1.0).min_size(px(120.0)).collapsible(),
+                    Pane::new(center).size(3.0).min_size(px(120.0)),
+                    Pane::new(right).size(1.0).min_size(px(120.0)).collapsible(),
+                ],
+                cx,
+            )
+        });
+        let vertical = cx.new(|cx| {
+            SplitPane::vertical(
+                [
+                    Pane::new(horizontal.clone()).size(3.0).min_size(px(120.0)),
+                    Pane::new(bottom).size(1.0).min_size(px(80.0)).collapsible(),
+                ],
+                cx,
+            )
+        });
+
+        let mut this = Self {
+            areas,
+            horizontal,
+            vertical,
+            detached: Vec::new(),
+        };
+        this.sync_collapsed(cx);
+        this
+    }
+
+    pub fn add_panel(&mut self, panel: DockPanel, zone: DockZone, cx: &mut Context<Self>) {
+        self.areas[zone.index()].update(cx, |area, cx| area.insert(panel, None, cx));
+        self.sync_collapsed(cx);
+    }
+
+    /// Moves the panel with `key` into `zone`, in front of the tab at `ix`
+    /// or behind all others.
+    pub fn move_panel(
+        &mut self,
+        key: &str,
+        zone: DockZone,
+        ix: Option<usize>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some((from, position, panel)) = self.take_panel(key, cx) else {
+            return;
+        };
+        // Removing the panel shifts the tabs behind it in the same zone.
+        let ix = match ix {
+            Some(ix) if from == Some(zone) && position.is_some_and(|position| position < ix) => {
+                Some(ix - 1)
+            }
+            ix => ix,
+        };
+        self.areas[zone.index()].update(cx, |area, cx| {
+            area.insert(panel, ix, cx);
+            area.collapsed = false;
+        });
+        self.sync_collapsed(cx);
+    }
+
+    /// Removes the panel from its zone or window.
+    fn take_panel(
+        &mut self,
+        key: &str,
+        cx: &mut Context<Self>,
+    ) -> Option<(Option<DockZone>, Option<usize>, DockPanel)> {
+        for zone in DockZone::ALL {
+            if let Some((ix, panel)) =
+                self.areas[zone.index()].update(cx, |area, cx| area.remove(key, cx))
+            {
+                return Some((Some(zone), Some(ix), panel));
+            }
+        }
+        let ix = self
+            .detached
+            .iter()
+            .position(|detached| detached.panel.key == key)?;
+        let detached = self.detached.remove(ix);
+        _ = detached
+            .window
+            .update(cx, |_, window, _| window.remove_window());
+        Some((None, None, detached.panel))
+    }
+
+    /// Shows the panel with `key` in a window of its own.
+    pub fn detach(&mut self, key: &str, cx: &mut Context<Self>) {
+        let attached = self
+            .areas
+            .iter()
+            .any(|area| area.read(cx).panels.iter().any(|panel| panel.key == key));
+        if !attached {
+            return;
+        }
+        let Some((Some(zone), _, panel)) = self.take_panel(key, cx) else {
+            return;
+        };
+        self.sync_collapsed(cx);
+
+        let dock = cx.weak_entity();
+        let detached_panel = panel.clone();
+        let window = cx.open_window(
+            WindowOptions {
+                titlebar: Some(TitlebarOptions {
+                    title: Some(panel.title.clone()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            move |window, cx| {
+                let key = detached_panel.key.clone();
+                let close_dock = dock.clone();
+                window.on_window_should_close(cx, move |_, cx| {
+                    _ = close_dock.update(cx, |dock, cx| dock.attach(&key, cx));
+                    true
+                });
+                cx.new(|_| DetachedPanel {
+                    dock,
+                    panel: detached_panel,
+                })
+            },
+        );
+        match window {
+            Ok(window) => self.detached.push(Detached {
+                panel,
+                zone,
+                window,
+            }),
+            Err(error) => {
+                tracing::error!("failed to detach panel `{}`: {error}", panel.key);
+                self.add_panel(panel, zone, cx);
+            }
+        }
+    }
+
+    /// Moves a detached panel back into the zone it came from. The caller is
+    /// responsible for closing its window.
+    fn attach(&mut self, key: &str, cx: &mut Context<Self>) {
+        let Some(ix) = self
+            .detached
+            .iter()
+            .position(|detached| detached.panel.key == key)
+        else {
+            return;
+        };
+        let detached = self.detached.remove(ix);
+        self.add_panel(detached.panel, detached.zone, cx);
+    }
+
+    pub fn is_collapsed(&self, zone: DockZone, cx: &App) -> bool {
+        self.areas[zone.index()].read(cx).collapsed
+    }
+
+    /// Collapses a dock to a strip at the window edge. The center can't be
+    /// collapsed.
+    pub fn set_collapsed(&mut self, zone: DockZone, collapsed: bool, cx: &mut Context<Self>) {
+        if zone == DockZone::Center {
+            return;
+        }
+        self.areas[zone.index()].update(cx, |area, cx| {
+            area.collapsed = collapsed;
+            cx.notify();
+        });
+        self.sync_collapsed(cx);
+    }
+
+    /// Hides docks which are collapsed or don't hold any panels.
+    fn sync_collapsed(&mut self, cx: &mut Context<Self>) {
+        let hidden = |zone: DockZone, cx: &App| {
+            let area = self.areas[zone.index()].read(cx);
+            area.collapsed || area.panels.is_empty()
+        };
+        let (left, right, bottom) = (
+            hidden(DockZone::Left, cx),
+            hidden(DockZone::Right, cx),
+            hidden(DockZone::Bottom, cx),
+        );
+        self.horizontal.update(cx, |split, cx| {
+            split.set_collapsed(0, left, cx);
+            split.set_collapsed(2, right, cx);
+        });
+        self.vertical
+            .update(cx, |split, cx| split.set_collapsed(1, bottom, cx));
+        cx.notify();
+    }
+
+    pub fn state(&self, cx: &App) -> DockState {
+        DockState {
+            zones: self
+                .areas
+                .iter()
+                .map(|area| {
+                    let area = area.read(cx);
+                    DockZoneState {
+                        zone: area.zone,
+                        panels: area
+                            .panels
+                            .iter()
+                            .map(|panel| panel.key.to_string())
+                            .collect(),
+                        active: area.active,
+                        collapsed: area.collapsed,
+                    }
+                })
+                .collect(),
+            horizontal: self.horizontal.read(cx).layout(),
+            vertical: self.vertical.read(cx).layout(),
+            detached: self
+                .detached
+                .iter()
+                .map(|detached| detached.panel.key.to_string())
+                .collect(),
+        }
+    }
+
+    /// Rearranges the panels as described by `state`. Panels not mentioned in
+    /// it stay where they are, unknown keys are ignored.
+    pub fn restore(&mut self, state: &DockState, cx: &mut Context<Self>) {
+        for zone_state in &state.zones {
+            for (ix, key) in zone_state.panels.iter().enumerate() {
+                self.move_panel(key, zone_state.zone, Some(ix), cx);
+            }
+            self.areas[zone_state.zone.index()].update(cx, |area, cx| {
+                area.active = zone_state.active.min(area.panels.len().saturating_sub(1));
+                area.collapsed = zone_state.collapsed && zone_state.zone != DockZone::Center;
+                cx.notify();
+            });
+        }
+        for key in &state.detached {
+            self.detach(key, cx);
+        }
+        self.horizontal
+            .update(cx, |split, cx| split.set_layout(&state.horizontal, cx));
+        self.vertical
+            .update(cx, |split, cx| split.set_layout(&state.vertical, cx));
+        self.sync_collapsed(cx);
+    }
+
+    /// Strip at the window edge listing the panels of a collapsed dock.
+    fn render_edge(&self, zone: DockZone, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let area = self.areas[zone.index()].read(cx);
+        if !area.collapsed || area.panels.is_empty() {
+            return None;
+        }
+        let selection = cx.theme().selection;
+        let buttons = area
+            .panels
+            .iter()
+            .enumerate()
+            .map(|(ix, panel)| {
+                Button::new(("dock-edge", ix))
+                    .px_1()
+                    .rounded(cx.theme().radii.sm)
+                    .hover(selection)
+                    .text_size(cx.theme().typography.text_xs)
+                    .child(panel.title.clone())
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        this.areas[zone.index()].update(cx, |area, _| area.active = ix);
+                        this.set_collapsed(zone, false, cx);
+                    }))
+            })
+            .collect::<Vec<_>>();
+
+        Some(
+            div()
+                .id(("dock-edge", zone.index()))
+                .flex()
+                .flex_none()
+                .gap_1()
+                .p_0p5()
+                .bg(cx.theme().foreground)
+                .map(|this| match zone {
+                    DockZone::Bottom => this.flex_row().w_full().h(EDGE_SIZE).items_center(),
+                    _ => this.flex_col().h_full().w(EDGE_SIZE),
+                })
+                .children(buttons),
+        )
+    }
+
+    /// Drop target at the window edge for docks which are currently hidden.
+    fn render_edge_drop_target(
+        &self,
+        zone: DockZone,
+        cx: &mut Context<Self>,
+    ) -> Option<impl IntoElement> {
+        let area = self.areas[zone.index()].read(cx);
+        if !area.collapsed && !area.panels.is_empty() {
+            return None;
+        }
+        let selection = cx.theme().selection;
+        Some(
+            div()
+                .id(("dock-edge-drop", zone.index()))
+                .absolute()
+                .map(|this| match zone {
+                    DockZone::Left => this.left_0().top_0().bottom_0().w(EDGE_SIZE),
+                    DockZone::Right => this.right_0().top_0().bottom_0().w(EDGE_SIZE),
+                    _ => this.left_0().right_0().bottom_0().h(EDGE_SIZE),
+                })
+                .drag_over::<DraggedPanel>(move |style, _, _, _| style.bg(selection.opacity(0.35)))
+                .on_drop(cx.listener(move |this, panel: &DraggedPanel, _, cx| {
+                    this.move_panel(&panel.key.clone(), zone, None, cx);
+                })),
+        )
+    }
+}
+
+impl Render for DockLayout {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let dragging = cx.has_active_drag();
+        let left = self.render_edge(DockZone::Left, cx);
+        let right = self.render_edge(DockZone::Right, cx);
+        let bottom = self.render_edge(DockZone::Bottom, cx);
+        let drop_targets = if dragging {
+            [DockZone::Left, DockZone::Right, DockZone::Bottom]
+                .into_iter()
+                .filter_map(|zone| self.render_edge_drop_target(zone, cx))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        v_flex()
+            .relative()
+            .size_full()
+            .child(
+                h_flex()
+                    .flex_1()
+                    .w_full()
+                    .overflow_hidden()
+                    .children(left)
+                    .child(div().flex_1().h_full().child(self.vertical.clone()))
+                    .children(right),
+            )
+            .children(bottom)
+            .children(drop_targets)
+    }
+}
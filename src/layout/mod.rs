@@ -1 +1,3 @@
-pub mod divider;
+pub mod divider;
+pub mod dock;
+pub mod split_pane;
@@ -0,0 +1,496 @@
+use std::{cell::Cell, rc::Rc};
+
+use crate::prelude::*;
+use gpui::{prelude::*, *};
+use serde::{Deserialize, Serialize};
+
+actions!(
+    split_pane,
+    [
+        /// Moves the focused divider left, or up in a vertical split.
+        MoveDividerBackward,
+        /// Moves the focused divider right, or down in a vertical split.
+        MoveDividerForward,
+        /// Collapses or expands the pane next to the focused divider.
+        ToggleCollapse
+    ]
+);
+
+/// Size of the draggable area between two panes.
+const HANDLE_SIZE: Pixels = px(5.0);
+/// Distance a divider moves per key press.
+const KEYBOARD_STEP: Pixels = px(16.0);
+
+pub fn init(cx: &mut App) {
+    // Only the arrows along the axis move a divider, the others are left to
+    // the surrounding views.
+    let horizontal = Some("SplitDivider && horizontal");
+    let vertical = Some("SplitDivider && vertical");
+    cx.bind_keys([
+        KeyBinding::new("left", MoveDividerBackward, horizontal),
+        KeyBinding::new("up", MoveDividerBackward, vertical),
+        KeyBinding::new("right", MoveDividerForward, horizontal),
+        KeyBinding::new("down", MoveDividerForward, vertical),
+        KeyBinding::new("enter", ToggleCollapse, Some("SplitDivider")),
+    ]);
+}
+
+/// A pane of a [`SplitPane`].
+pub struct Pane {
+    view: AnyView,
+    size: f32,
+    default_size: f32,
+    min_size: Pixels,
+    max_size: Option<Pixels>,
+    collapsible: bool,
+    collapsed: bool,
+}
+
+impl Pane {
+    pub fn new(view: impl Into<AnyView>) -> Self {
+        Self {
+            view: view.into(),
+            size: 1.0,
+            default_size: 1.0,
+            min_size: px(0.0),
+            max_size: None,
+            collapsible: false,
+            collapsed: false,
+        }
+    }
+
+    /// Initial size relative to the other panes, which is restored when the
+    /// adjacent divider is double-clicked.
+    pub fn size(self, size: f32) -> Self {
+        Self {
+            size,
+            default_size: size,
+            ..self
+        }
+    }
+
+    pub fn min_size(self, size: impl Into<Pixels>) -> Self {
+        Self {
+            min_size: size.into(),
+            ..self
+        }
+    }
+
+    pub fn max_size(self, size: impl Into<Pixels>) -> Self {
+        Self {
+            max_size: Some(size.into()),
+            ..self
+        }
+    }
+
+    pub fn collapsible(self) -> Self {
+        Self {
+            collapsible: true,
+            ..self
+        }
+    }
+}
+
+/// Pane sizes and collapsed states of a [`SplitPane`], e.g. to restore them
+/// after a restart. Emitted by the split pane whenever the user changes it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SplitLayout {
+    pub sizes: Vec<f32>,
+    pub collapsed: Vec<bool>,
+}
+
+#[derive(Clone)]
+struct DraggedDivider {
+    split: EntityId,
+    /// Index of the pane in front of the divider.
+    ix: usize,
+}
+
+impl Render for DraggedDivider {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        Empty
+    }
+}
+
+/// Lays out its panes next to each other along `axis`, separated by
+/// dividers that can be dragged or moved with the arrow keys once focused.
+pub struct SplitPane {
+    axis: Axis,
+    panes: Vec<Pane>,
+    divider_focus: Vec<FocusHandle>,
+    bounds: Rc<Cell<Option<Bounds<Pixels>>>>,
+}
+
+impl EventEmitter<SplitLayout> for SplitPane {}
+
+impl SplitPane {
+    pub fn new(axis: Axis, panes: impl IntoIterator<Item = Pane>, cx: &mut Context<Self>) -> Self {
+        let panes = panes.into_iter().collect::<Vec<_>>();
+        Self {
+            axis,
+            divider_focus: panes.iter().map(|_| cx.focus_handle()).collect(),
+            panes,
+            bounds: Rc::new(Cell::new(None)),
+        }
+    }
+
+    /// Panes next to each other, from left to right.
+    pub fn horizontal(panes: impl IntoIterator<Item = Pane>, cx: &mut Context<Self>) -> Self {
+        Self::new(Axis::Horizontal, panes, cx)
+    }
+
+    /// Panes on top of each other, from top to bottom.
+    pub fn vertical(panes: impl IntoIterator<Item = Pane>, cx: &mut Context<Self>) -> Self {
+        Self::new(Axis::Vertical, panes, cx)
+    }
+
+    pub fn push_pane(&mut self, pane: Pane, cx: &mut Context<Self>) {
+        self.panes.push(pane);
+        self.divider_focus.push(cx.focus_handle());
+        cx.notify();
+    }
+
+    pub fn layout(&self) -> SplitLayout {
+        SplitLayout {
+            sizes: self.panes.iter().map(|pane| pane.size).collect(),
+            collapsed: self.panes.iter().map(|pane| pane.collapsed).collect(),
+        }
+    }
+
+    /// Applies a layout saved with [`SplitPane::layout`]. Layouts stored for a
+    /// different number of panes are ignored.
+    pub fn set_layout(&mut self, layout: &SplitLayout, cx: &mut Context<Self>) {
+        if layout.sizes.len() != self.panes.len() {
+            return;
+        }
+        for (ix, pane) in self.panes.iter_mut().enumerate() {
+            pane.size = layout.sizes[ix].max(0.0);
+            pane.collapsed = pane.collapsible && layout.collapsed.get(ix).copied().unwrap_or(false);
+        }
+        cx.notify();
+    }
+
+    pub fn is_collapsed(&self, ix: usize) -> bool {
+        self.panes.get(ix).is_some_and(|pane| pane.collapsed)
+    }
+
+    pub fn set_collapsed(&mut self, ix: usize, collapsed: bool, cx: &mut Context<Self>) {
+        let Some(pane) = self.panes.get_mut(ix) else {
+            return;
+        };
+        if !pane.collapsible || pane.collapsed == collapsed {
+            return;
+        }
+        pane.collapsed = collapsed;
+        cx.emit(self.layout());
+        cx.notify();
+    }
+
+    fn along(&self, point: Point<Pixels>) -> Pixels {
+        match self.axis {
+            Axis::Horizontal => point.x,
+            Axis::Vertical => point.y,
+        }
+    }
+
+    fn length(&self, size: Size<Pixels>) -> Pixels {
+        match self.axis {
+            Axis::Horizontal => size.width,
+            Axis::Vertical => size.height,
+        }
+    }
+
+    fn visible(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.panes.len()).filter(|ix| !self.panes[*ix].collapsed)
+    }
+
+    /// Returns the pane behind the divider behind pane `ix`, if both panes
+    /// next to the divider are visible so it can resize them.
+    fn resizable(&self, ix: usize) -> Option<usize> {
+        let next = ix + 1;
+        (next < self.panes.len() && !self.is_collapsed(ix) && !self.is_collapsed(next))
+            .then_some(next)
+    }
+
+    /// Pixels per unit of pane size, given the current bounds.
+    fn pixels_per_unit(&self) -> Option<f32> {
+        let bounds = self.bounds.get()?;
+        let dividers = self.panes.len().saturating_sub(1);
+        let total = self.visible().map(|ix| self.panes[ix].size).sum::<f32>();
+        let available = self.length(bounds.size) - HANDLE_SIZE * dividers as f32;
+        (total > 0.0).then(|| f32::from(available) / total)
+    }
+
+    /// Moves the divider behind pane `ix` by `delta`, respecting the size
+    /// limits of both panes next to it.
+    fn move_divider(&mut self, ix: usize, delta: Pixels, cx: &mut Context<Self>) {
+        let (Some(next), Some(scale)) = (self.resizable(ix), self.pixels_per_unit()) else {
+            return;
+        };
+        let (a, b) = (&self.panes[ix], &self.panes[next]);
+        let total = (a.size + b.size) * scale;
+        let min = f32::from(a.min_size).max(total - b.max_size.map_or(total, f32::from));
+        let max = a
+            .max_size
+            .map_or(total, f32::from)
+            .min(total - f32::from(b.min_size));
+        if min > max {
+            return;
+        }
+        let size = (a.size * scale + f32::from(delta)).clamp(min, max);
+        self.panes[ix].size = size / scale;
+        self.panes[next].size = (total - size) / scale;
+        cx.emit(self.layout());
+        cx.notify();
+    }
+
+    fn drag_divider(&mut self, ix: usize, position: Pixels, cx: &mut Context<Self>) {
+        let (Some(bounds), Some(scale)) = (self.bounds.get(), self.pixels_per_unit()) else {
+            return;
+        };
+        let start = self.along(bounds.origin)
+            + (0..ix).fold(px(0.0), |start, before| {
+                let size = if self.is_collapsed(before) {
+                    0.0
+                } else {
+                    self.panes[before].size * scale
+                };
+                start + px(size) + HANDLE_SIZE
+            });
+        let divider = start + px(self.panes[ix].size * scale) + HANDLE_SIZE / 2.0;
+        self.move_divider(ix, position - divider, cx);
+    }
+
+    /// Gives the panes next to the divider behind pane `ix` their default
+    /// proportions again.
+    fn reset_divider(&mut self, ix: usize, cx: &mut Context<Self>) {
+        let Some(next) = self.resizable(ix) else {
+            return;
+        };
+        let total = self.panes[ix].size + self.panes[next].size;
+        let defaults = self.panes[ix].default_size + self.panes[next].default_size;
+        if defaults <= 0.0 {
+            return;
+        }
+        self.panes[ix].size = total * self.panes[ix].default_size / defaults;
+        self.panes[next].size = total * self.panes[next].default_size / defaults;
+        cx.emit(self.layout());
+        cx.notify();
+    }
+
+    fn focused_divider(&self, window: &Window) -> Option<usize> {
+        self.divider_focus
+            .iter()
+            .position(|focus| focus.is_focused(window))
+    }
+
+    fn move_divider_backward(
+        &mut self,
+        _: &MoveDividerBackward,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(ix) = self.focused_divider(window) {
+            self.move_divider(ix, -KEYBOARD_STEP, cx);
+        }
+    }
+
+    fn move_divider_forward(
+        &mut self,
+        _: &MoveDividerForward,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(ix) = self.focused_divider(window) {
+            self.move_divider(ix, KEYBOARD_STEP, cx);
+        }
+    }
+
+    fn toggle_collapse(&mut self, _: &ToggleCollapse, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(ix) = self.focused_divider(window) else {
+            return;
+        };
+        // Expand a collapsed pane next to the divider first, otherwise
+        // prefer collapsing the pane in front of it.
+        let neighbours = [ix, ix + 1];
+        let collapsible = |ix: &usize| self.panes.get(*ix).is_some_and(|pane| pane.collapsible);
+        let target = neighbours
+            .into_iter()
+            .filter(collapsible)
+            .find(|ix| self.panes[*ix].collapsed)
+            .or_else(|| neighbours.into_iter().find(collapsible));
+        if let Some(target) = target {
+            self.set_collapsed(target, !self.panes[target].collapsed, cx);
+        }
+    }
+
+    fn render_divider(
+        &self,
+        ix: usize,
+        window: &Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let split = cx.entity_id();
+        let resizable = self.resizable(ix).is_some();
+        let focused = self.divider_focus[ix].is_focused(window);
+        let color = if focused {
+            cx.theme().selection
+        } else {
+            cx.theme().border
+        };
+
+        div()
+            .id(("split-divider", ix))
+            .track_focus(&self.divider_focus[ix])
+            .key_context(match self.axis {
+                Axis::Horizontal => "SplitDivider horizontal",
+                Axis::Vertical => "SplitDivider vertical",
+            })
+            .flex()
+            .flex_none()
+            .items_center()
+            .justify_center()
+            .map(|this| match self.axis {
+                Axis::Horizontal => this
+                    .w(HANDLE_SIZE)
+                    .h_full()
+                    .when(resizable, |this| this.cursor(CursorStyle::ResizeLeftRight))
+                    .child(Divider::vertical().color(color)),
+                Axis::Vertical => this
+                    .h(HANDLE_SIZE)
+                    .w_full()
+                    .when(resizable, |this| this.cursor(CursorStyle::ResizeUpDown))
+                    .child(Divider::horizontal().color(color)),
+            })
+            .when(resizable, |this| {
+                this.on_drag(DraggedDivider { split, ix }, |divider, _, _, cx| {
+                    cx.new(|_| divider.clone())
+                })
+            })
+            .on_click(cx.listener(move |this, event: &ClickEvent, window, cx| {
+                if event.click_count() == 2 {
+                    this.reset_divider(ix, cx);
+                } else {
+                    window.focus(&this.divider_focus[ix]);
+                }
+            }))
+    }
+}
+
+impl Render for SplitPane {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let bounds = self.bounds.clone();
+        let axis = self.axis;
+
+        // Dividers next to collapsed panes stay, so the panes can be expanded
+        // again from the keyboard.
+        let mut children = Vec::with_capacity(self.panes.len() * 2);
+        for (ix, pane) in self.panes.iter().enumerate() {
+            if ix > 0 {
+                children.push(self.render_divider(ix - 1, window, cx).into_any_element());
+            }
+            if pane.collapsed {
+                continue;
+            }
+            children.push(
+                div()
+                    .overflow_hidden()
+                    .map(|mut this| {
+                        // Let flexbox share the space in proportion to the sizes.
+                        let style = this.style();
+                        style.flex_grow = Some(pane.size);
+                        style.flex_shrink = Some(1.0);
+                        style.flex_basis = Some(px(0.0).into());
+                        this
+                    })
+                    .map(|this| match axis {
+                        Axis::Horizontal => this
+                            .h_full()
+                            .min_w(pane.min_size)
+                            .when_some(pane.max_size, |this, max| this.max_w(max)),
+                        Axis::Vertical => this
+                            .w_full()
+                            .min_h(pane.min_size)
+                            .when_some(pane.max_size, |this, max| this.max_h(max)),
+                    })
+                    .child(pane.view.clone())
+                    .into_any_element(),
+            );
+        }
+
+        div()
+            .id("split-pane")
+            .relative()
+            .flex()
+            .size_full()
+            .map(|this| match axis {
+                Axis::Horizontal => this.flex_row(),
+                Axis::Vertical => this.flex_col(),
+            })
+            .on_action(cx.listener(Self::move_divider_backward))
+            .on_action(cx.listener(Self::move_divider_forward))
+            .on_action(cx.listener(Self::toggle_collapse))
+            .on_drag_move(
+                cx.listener(move |this, event: &DragMoveEvent<DraggedDivider>, _, cx| {
+                    let divider = event.drag(cx);
+                    if divider.split == cx.entity_id() {
+                        let ix = divider.ix;
+                        let position = this.along(event.event.position);
+                        this.drag_divider(ix, position, cx);
+                    }
+                }),
+            )
+            .child(
+                canvas(move |b, _, _| bounds.set(Some(b)), |_, _, _, _| {})
+                    .absolute()
+                    .size_full(),
+            )
+            .children(children)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Blank;
+
+    impl Render for Blank {
+        fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+            Empty
+        }
+    }
+
+    #[gpui::test]
+    fn move_divider_respects_pane_limits(cx: &mut TestAppContext) {
+        let split = cx.update(|cx| {
+            let front = Pane::new(cx.new(|_| Blank))
+                .min_size(px(20.0))
+                .max_size(px(150.0));
+            let back = Pane::new(cx.new(|_| Blank))
+                .min_size(px(30.0))
+                .collapsible();
+            cx.new(|cx| SplitPane::horizontal([front, back], cx))
+        });
+        split.update(cx, |split, cx| {
+            // 200px for the panes next to one divider, 100px per unit.
+            split.bounds.set(Some(Bounds::new(
+                point(px(0.0), px(0.0)),
+                size(HANDLE_SIZE + px(200.0), px(100.0)),
+            )));
+            let sizes = |split: &SplitPane| (split.panes[0].size, split.panes[1].size);
+
+            split.move_divider(0, px(40.0), cx);
+            assert_eq!(sizes(split), (1.4, 0.6));
+            split.move_divider(0, px(500.0), cx);
+            assert_eq!(sizes(split), (1.5, 0.5));
+            split.move_divider(0, px(-500.0), cx);
+            let (front, back) = sizes(split);
+            assert!((front - 0.2).abs() < 1e-5 && (back - 1.8).abs() < 1e-5);
+
+            split.set_collapsed(1, true, cx);
+            split.move_divider(0, px(40.0), cx);
+            assert_eq!(sizes(split), (front, back));
+        });
+    }
+}
@@ -1,12 +1,15 @@
 #![allow(clippy::type_complexity)]
 
+pub mod animation;
 pub mod button;
 pub mod checkbox;
+pub mod fuzzy;
 pub mod input;
 pub mod label;
 pub mod layout;
 pub mod navigation;
 pub mod overlay;
+pub mod select;
 pub mod shader;
 pub mod spinner;
 pub mod theme;
@@ -20,13 +23,25 @@ pub mod prelude {
     pub use crate::input::TextInput;
     pub use crate::label::*;
     use crate::layout;
-    pub use crate::navigation::tab_bar::{Tab, TabBar};
+    use crate::navigation;
+    pub use crate::navigation::{
+        menu_bar::{AppMenu, MenuBar},
+        tab_bar::{Tab, TabBar, TabBarEvent},
+        tabs::{TabItem, Tabs},
+    };
+    use crate::overlay;
     pub use crate::overlay::{
+        command_palette::CommandPalette,
         menu::CtxMenu,
-        menu_builder::{MenuBuilder, MenuListItem},
+        menu_builder::{MenuBuilder, MenuEntry, MenuListItem, MenuToggle},
+        menu_definition::{MenuDefinition, MenuItemDefinition},
+        modal::{self, Dialog, ModalLayer},
+        notification::{NotificationHistory, NotificationStack, Notifications, Severity, Toast},
+        popover::{Popover, PopoverHandle},
         popup::Popup,
         tooltip::Tooltip,
     };
+    pub use crate::select::Select;
     pub use crate::shader::{
         self,
         element::{Shader, ShaderOptions},
@@ -34,15 +49,35 @@ pub mod prelude {
         IntoBuffer,
     };
     pub use crate::spinner::*;
-    pub use crate::theme::{self, AppTheme, Theme};
+    pub use crate::animation::{self, Easing, Interaction, Lerp, Transition};
+    pub use crate::theme::{
+        self, AppTheme, Theme, ThemeWatcher,
+        audit::{ContrastIssue, ContrastPair},
+        registry::{ThemeMode, ThemeRegistry, ThemeSelection},
+        scope::ThemeScope,
+        tokens::box_shadows,
+    };
     pub use crate::util::{self, *};
-    pub use crate::window::WindowTopBar;
+    pub use crate::window::{
+        TopBarStyle, WindowTopBar,
+        geometry::{WindowGeometry, WindowStates},
+    };
     pub use gpui;
     pub use layout::divider::{self, Divider};
+    pub use layout::dock::{DockLayout, DockPanel, DockState, DockZone};
+    pub use layout::split_pane::{Pane, SplitLayout, SplitPane};
 
     pub fn init_all(cx: &mut gpui::App) {
         theme::init(cx);
         input::init(cx);
+        overlay::menu_builder::init(cx);
+        overlay::command_palette::init(cx);
+        overlay::popover::init(cx);
+        overlay::modal::init(cx);
+        navigation::menu_bar::init(cx);
+        navigation::tabs::init(cx);
+        layout::split_pane::init(cx);
+        crate::select::init(cx);
         shader::init(cx);
     }
 }
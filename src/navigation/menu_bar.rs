@@ -0,0 +1,541 @@
+use std::{cell::RefCell, collections::HashMap, ops::Range, rc::Rc};
+
+use crate::overlay::menu_builder::{SelectChild, SelectParent};
+use crate::prelude::*;
+use gpui::{prelude::*, *};
+
+actions!(
+    menu_bar,
+    [
+        /// Toggles keyboard navigation of the menu bar.
+        Activate,
+        /// Leaves keyboard navigation and closes the open menu.
+        Deactivate,
+        /// Highlights the menu to the right.
+        SelectNextMenu,
+        /// Highlights the menu to the left.
+        SelectPreviousMenu,
+        /// Opens the highlighted menu.
+        OpenMenu,
+    ]
+);
+
+pub fn init(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("f10", Activate, None),
+        KeyBinding::new("escape", Deactivate, Some("MenuBar")),
+        KeyBinding::new("right", SelectNextMenu, Some("MenuBar")),
+        KeyBinding::new("left", SelectPreviousMenu, Some("MenuBar")),
+        KeyBinding::new("down", OpenMenu, Some("MenuBar")),
+        KeyBinding::new("enter", OpenMenu, Some("MenuBar")),
+        KeyBinding::new("space", OpenMenu, Some("MenuBar")),
+    ]);
+}
+
+/// A top level menu of a [`MenuBar`]. Titles and labels may mark their
+/// mnemonic with a leading `&`, e.g. `&File`; use `&&` for a literal `&`.
+#[derive(Clone)]
+pub struct AppMenu {
+    title: SharedString,
+    items: Vec<AppMenuItem>,
+}
+
+pub enum AppMenuItem {
+    Action {
+        label: SharedString,
+        action: Box<dyn Action>,
+    },
+    Entry {
+        label: SharedString,
+        handler: Rc<dyn Fn(&mut Window, &mut App)>,
+    },
+    Submenu(AppMenu),
+    Separator,
+}
+
+impl Clone for AppMenuItem {
+    fn clone(&self) -> Self {
+        match self {
+            AppMenuItem::Action { label, action } => AppMenuItem::Action {
+                label: label.clone(),
+                action: action.boxed_clone(),
+            },
+            AppMenuItem::Entry { label, handler } => AppMenuItem::Entry {
+                label: label.clone(),
+                handler: handler.clone(),
+            },
+            AppMenuItem::Submenu(menu) => AppMenuItem::Submenu(menu.clone()),
+            AppMenuItem::Separator => AppMenuItem::Separator,
+        }
+    }
+}
+
+impl AppMenu {
+    pub fn new(title: impl Into<SharedString>) -> Self {
+        Self {
+            title: title.into(),
+            items: Vec::new(),
+        }
+    }
+
+    pub fn action(mut self, label: impl Into<SharedString>, action: impl Action) -> Self {
+        self.items.push(AppMenuItem::Action {
+            label: label.into(),
+            action: Box::new(action),
+        });
+        self
+    }
+
+    pub fn entry(
+        mut self,
+        label: impl Into<SharedString>,
+        handler: impl Fn(&mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.items.push(AppMenuItem::Entry {
+            label: label.into(),
+            handler: Rc::new(handler),
+        });
+        self
+    }
+
+    pub fn submenu(mut self, menu: AppMenu) -> Self {
+        self.items.push(AppMenuItem::Submenu(menu));
+        self
+    }
+
+    pub fn separator(mut self) -> Self {
+        self.items.push(AppMenuItem::Separator);
+        self
+    }
+
+    fn build(&self, mut menu: MenuBuilder) -> MenuBuilder {
+        for item in &self.items {
+            menu = match item {
+                AppMenuItem::Action { label, action } => {
+                    menu.action(parse_mnemonic(label).0, action.boxed_clone())
+                }
+                AppMenuItem::Entry { label, handler } => {
+                    let handler = handler.clone();
+                    menu.entry(parse_mnemonic(label).0, move |window, cx| {
+                        handler(window, cx)
+                    })
+                }
+                AppMenuItem::Submenu(submenu) => {
+                    let label = parse_mnemonic(&submenu.title).0;
+                    let submenu = submenu.clone();
+                    menu.submenu(
+                        {
+                            let label = label.clone();
+                            move |_, cx| {
+                                div()
+                                    .px(cx.theme().spacing.md)
+                                    .py_0p5()
+                                    .text_color(cx.theme().text)
+                                    .child(label.clone())
+                                    .into_any_element()
+                            }
+                        },
+                        move |menu, _, _| submenu.build(menu),
+                    )
+                    .search_label(label)
+                }
+                AppMenuItem::Separator => menu.separator(),
+            };
+        }
+        menu
+    }
+}
+
+/// Strips the `&` markers from `label` and returns the byte range and the
+/// lowercased character of the mnemonic, if any.
+fn parse_mnemonic(label: &str) -> (SharedString, Option<(Range<usize>, char)>) {
+    let mut text = String::with_capacity(label.len());
+    let mut mnemonic = None;
+    let mut chars = label.chars();
+    while let Some(c) = chars.next() {
+        if c != '&' {
+            text.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('&') => text.push('&'),
+            Some(c) => {
+                if mnemonic.is_none() {
+                    let start = text.len();
+                    mnemonic = Some((
+                        start..start + c.len_utf8(),
+                        c.to_lowercase().next().unwrap_or(c),
+                    ));
+                }
+                text.push(c);
+            }
+            None => {}
+        }
+    }
+    (text.into(), mnemonic)
+}
+
+struct OpenAppMenu {
+    index: usize,
+    menu: Entity<MenuBuilder>,
+    _subscription: Subscription,
+}
+
+/// In-window application menu bar for windows drawing their own title bar.
+///
+/// Titles occlude the element behind them, so the bar can be placed inside a
+/// `WindowControlArea::Drag` region. Alt and F10 only reach the bar if the
+/// root element of the window is wrapped with [`MenuBar::capture_keys`].
+pub struct MenuBar {
+    menus: Vec<AppMenu>,
+    focus: FocusHandle,
+    open: Option<OpenAppMenu>,
+    highlighted: Option<usize>,
+    keyboard_active: bool,
+    alt_held: bool,
+    alt_tap: bool,
+    previous_focus: Option<FocusHandle>,
+    title_bounds: Rc<RefCell<HashMap<usize, Bounds<Pixels>>>>,
+}
+
+impl MenuBar {
+    pub fn new(menus: Vec<AppMenu>, cx: &mut Context<Self>) -> Self {
+        Self {
+            menus,
+            focus: cx.focus_handle(),
+            open: None,
+            highlighted: None,
+            keyboard_active: false,
+            alt_held: false,
+            alt_tap: false,
+            previous_focus: None,
+            title_bounds: Default::default(),
+        }
+    }
+
+    pub fn set_menus(&mut self, menus: Vec<AppMenu>, cx: &mut Context<Self>) {
+        self.menus = menus;
+        self.open = None;
+        self.highlighted = None;
+        cx.notify();
+    }
+
+    /// Registers the alt, alt+mnemonic and F10 handlers of `menu_bar` on
+    /// `element`, which should be the root element of the window.
+    pub fn capture_keys<E: InteractiveElement>(menu_bar: &Entity<Self>, element: E) -> E {
+        let on_activate = menu_bar.downgrade();
+        let on_modifiers = menu_bar.downgrade();
+        let on_key = menu_bar.downgrade();
+        element
+            .on_action(move |_: &Activate, window, cx| {
+                _ = on_activate.update(cx, |this, cx| this.toggle_keyboard(window, cx));
+            })
+            .on_modifiers_changed(move |event: &ModifiersChangedEvent, window, cx| {
+                _ = on_modifiers.update(cx, |this, cx| {
+                    this.modifiers_changed(event.modifiers, window, cx)
+                });
+            })
+            .capture_key_down(move |event: &KeyDownEvent, window, cx| {
+                _ = on_key.update(cx, |this, cx| {
+                    this.alt_tap = false;
+                    let modifiers = event.keystroke.modifiers;
+                    let only_alt = modifiers.alt
+                        && !modifiers.control
+                        && !modifiers.platform
+                        && !modifiers.shift;
+                    if only_alt && this.open_mnemonic(&event.keystroke.key, window, cx) {
+                        cx.stop_propagation();
+                    }
+                });
+            })
+    }
+
+    fn modifiers_changed(
+        &mut self,
+        modifiers: Modifiers,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let only_alt = modifiers.alt
+            && !modifiers.control
+            && !modifiers.platform
+            && !modifiers.shift
+            && !modifiers.function;
+        if only_alt {
+            self.alt_tap = true;
+        } else if self.alt_tap && !modifiers.modified() {
+            self.alt_tap = false;
+            self.toggle_keyboard(window, cx);
+        } else {
+            self.alt_tap = false;
+        }
+        if self.alt_held != modifiers.alt {
+            self.alt_held = modifiers.alt;
+            cx.notify();
+        }
+    }
+
+    fn toggle_keyboard(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.keyboard_active || self.open.is_some() {
+            self.deactivate(&Deactivate, window, cx);
+            return;
+        }
+        if self.menus.is_empty() {
+            return;
+        }
+        self.previous_focus = window.focused(cx);
+        self.keyboard_active = true;
+        self.highlighted = Some(0);
+        window.focus(&self.focus);
+        cx.notify();
+    }
+
+    fn deactivate(&mut self, _: &Deactivate, window: &mut Window, cx: &mut Context<Self>) {
+        self.open = None;
+        self.keyboard_active = false;
+        self.highlighted = None;
+        if let Some(previous_focus) = self.previous_focus.take() {
+            window.focus(&previous_focus);
+        }
+        cx.notify();
+    }
+
+    fn select_next_menu(
+        &mut self,
+        _: &SelectNextMenu,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.step(1, window, cx);
+    }
+
+    fn select_previous_menu(
+        &mut self,
+        _: &SelectPreviousMenu,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.step(self.menus.len().saturating_sub(1), window, cx);
+    }
+
+    fn open_highlighted(&mut self, _: &OpenMenu, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.highlighted {
+            self.open_menu(ix, window, cx);
+        }
+    }
+
+    /// Moves the highlight `offset` menus to the right, wrapping around, and
+    /// keeps a menu open if one already is.
+    fn step(&mut self, offset: usize, window: &mut Window, cx: &mut Context<Self>) {
+        if self.menus.is_empty() {
+            return;
+        }
+        let current = self
+            .open
+            .as_ref()
+            .map(|open| open.index)
+            .or(self.highlighted)
+            .unwrap_or(0);
+        let ix = (current + offset) % self.menus.len();
+        if self.open.is_some() {
+            self.open_menu(ix, window, cx);
+        } else {
+            self.highlighted = Some(ix);
+            cx.notify();
+        }
+    }
+
+    fn open_mnemonic(&mut self, key: &str, window: &mut Window, cx: &mut Context<Self>) -> bool {
+        let mut chars = key.chars();
+        let (Some(c), None) = (chars.next(), chars.next()) else {
+            return false;
+        };
+        let c = c.to_lowercase().next().unwrap_or(c);
+        let Some(ix) = self
+            .menus
+            .iter()
+            .position(|menu| parse_mnemonic(&menu.title).1.is_some_and(|(_, m)| m == c))
+        else {
+            return false;
+        };
+        if self.previous_focus.is_none() && !self.keyboard_active {
+            self.previous_focus = window.focused(cx);
+        }
+        self.keyboard_active = true;
+        self.open_menu(ix, window, cx);
+        true
+    }
+
+    fn on_key_down(&mut self, event: &KeyDownEvent, window: &mut Window, cx: &mut Context<Self>) {
+        let modifiers = event.keystroke.modifiers;
+        if modifiers.control || modifiers.platform {
+            return;
+        }
+        if self.open_mnemonic(&event.keystroke.key, window, cx) {
+            cx.stop_propagation();
+        }
+    }
+
+    fn open_menu(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        if self.open.as_ref().is_some_and(|open| open.index == ix) {
+            return;
+        }
+        let Some(app_menu) = self.menus.get(ix).cloned() else {
+            return;
+        };
+        if self.open.is_none() && !self.keyboard_active {
+            self.previous_focus = window.focused(cx);
+        }
+
+        let menu = MenuBuilder::build(window, cx, move |menu, _, _| app_menu.build(menu));
+        let subscription =
+            cx.subscribe_in(&menu, window, |this, menu, _: &DismissEvent, window, cx| {
+                // a menu replaced by hovering another title is dismissed too
+                if this.open.as_ref().is_some_and(|open| &open.menu == menu) {
+                    this.deactivate(&Deactivate, window, cx);
+                }
+            });
+        window.focus(&menu.focus_handle(cx));
+        self.highlighted = Some(ix);
+        self.open = Some(OpenAppMenu {
+            index: ix,
+            menu,
+            _subscription: subscription,
+        });
+        cx.notify();
+    }
+
+    fn toggle_menu(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        if self.open.as_ref().is_some_and(|open| open.index == ix) {
+            self.deactivate(&Deactivate, window, cx);
+        } else {
+            self.open_menu(ix, window, cx);
+        }
+    }
+
+    fn render_title(&self, ix: usize, menu: &AppMenu, cx: &mut Context<Self>) -> impl IntoElement {
+        let (title, mnemonic) = parse_mnemonic(&menu.title);
+        let show_mnemonic = self.keyboard_active || self.alt_held;
+        let title_bounds = self.title_bounds.clone();
+
+        Button::new(("menu-bar-title", ix))
+            .occlude()
+            .h_full()
+            .px(cx.theme().spacing.md)
+            .justify_center()
+            .hover(cx.theme().selection)
+            .when(self.highlighted == Some(ix), |this| {
+                this.base(cx.theme().selection)
+            })
+            .child(
+                canvas(
+                    move |bounds, _, _| {
+                        title_bounds.borrow_mut().insert(ix, bounds);
+                    },
+                    |_, _, _, _| {},
+                )
+                .absolute()
+                .size_full(),
+            )
+            .map(|this| match mnemonic {
+                Some((range, _)) if show_mnemonic => {
+                    this.child(StyledText::new(title).with_highlights([(
+                        range,
+                        HighlightStyle {
+                            underline: Some(UnderlineStyle {
+                                thickness: px(1.0),
+                                color: None,
+                                wavy: false,
+                            }),
+                            ..Default::default()
+                        },
+                    )]))
+                }
+                _ => this.child(title),
+            })
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |this, _, window, cx| {
+                    cx.stop_propagation();
+                    this.toggle_menu(ix, window, cx);
+                }),
+            )
+            .on_mouse_move(cx.listener(move |this, _, window, cx| {
+                // once a menu is open, hovering another title switches to it
+                if this.open.as_ref().is_some_and(|open| open.index != ix) {
+                    this.open_menu(ix, window, cx);
+                }
+            }))
+    }
+}
+
+impl Focusable for MenuBar {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus.clone()
+    }
+}
+
+impl Render for MenuBar {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let popup = self.open.as_ref().and_then(|open| {
+            let bounds = *self.title_bounds.borrow().get(&open.index)?;
+            Some(
+                deferred(
+                    anchored()
+                        .anchor(Corner::TopLeft)
+                        .position(bounds.bottom_left())
+                        .snap_to_window_with_margin(px(8.0))
+                        .child(div().occlude().child(open.menu.clone())),
+                )
+                .with_priority(1),
+            )
+        });
+
+        h_flex()
+            .id("menu-bar")
+            .key_context("MenuBar")
+            .track_focus(&self.focus)
+            .h_full()
+            .items_center()
+            .gap_0p5()
+            .text_color(cx.theme().text)
+            .on_action(cx.listener(Self::deactivate))
+            .on_action(cx.listener(Self::select_next_menu))
+            .on_action(cx.listener(Self::select_previous_menu))
+            .on_action(cx.listener(Self::open_highlighted))
+            // arrows the open menu has no use for move along the bar
+            .on_action(cx.listener(|this, _: &SelectChild, window, cx| {
+                this.select_next_menu(&SelectNextMenu, window, cx)
+            }))
+            .on_action(cx.listener(|this, _: &SelectParent, window, cx| {
+                this.select_previous_menu(&SelectPreviousMenu, window, cx)
+            }))
+            .on_key_down(cx.listener(Self::on_key_down))
+            .children(
+                self.menus
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, menu)| self.render_title(ix, menu, cx))
+                    .collect::<Vec<_>>(),
+            )
+            .children(popup)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::parse_mnemonic;
+
+    #[test]
+    fn parses_mnemonics() {
+        let (text, mnemonic) = parse_mnemonic("&File");
+        assert_eq!(text.as_ref(), "File");
+        assert_eq!(mnemonic, Some((0..1, 'f')));
+
+        let (text, mnemonic) = parse_mnemonic("Save && E&xit");
+        assert_eq!(text.as_ref(), "Save & Exit");
+        assert_eq!(mnemonic, Some((8..9, 'x')));
+
+        assert_eq!(parse_mnemonic("Edit").1, None);
+    }
+}
@@ -1 +1,3 @@
-pub mod tab_bar;
+pub mod menu_bar;
+pub mod tab_bar;
+pub mod tabs;
@@ -1,13 +1,54 @@
+use std::rc::Rc;
+
 use crate::prelude::*;
 use gpui::{prelude::*, *};
 
+/// Distance the tabs scroll per click on the overflow buttons.
+const SCROLL_STEP: Pixels = px(120.0);
+
+/// Requests from a [`TabBar`] to its owner, which updates its model
+/// accordingly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TabBarEvent {
+    /// The tab at the index was clicked.
+    Activated(usize),
+    /// The close button of the tab was clicked or the tab middle-clicked.
+    Closed(usize),
+    /// A tab was dragged from `from` and dropped in front of the tab at `to`,
+    /// or behind the last tab of its group if `to` is the index after it.
+    Reordered { from: usize, to: usize },
+}
+
+#[derive(Clone)]
+struct DraggedTab {
+    bar: ElementId,
+    ix: usize,
+}
+
+impl Render for DraggedTab {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .w_16()
+            .h_6()
+            .bg(cx.theme().selection)
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded(cx.theme().radii.lg)
+            .opacity(0.8)
+    }
+}
+
 #[derive(IntoElement)]
 pub struct TabBar {
     id: ElementId,
     focus: FocusHandle,
+    tabs: Vec<Tab>,
     children: Vec<AnyElement>,
+    on_event: Option<Rc<dyn Fn(&TabBarEvent, &mut Window, &mut App)>>,
+    reorderable: bool,
 }
 
+/// Children are rendered behind the tabs and don't scroll with them.
 impl ParentElement for TabBar {
     fn extend(&mut self, elements: impl IntoIterator<Item = AnyElement>) {
         self.children.extend(elements);
@@ -19,45 +60,276 @@ impl TabBar {
         Self {
             id: id.into(),
             focus,
+            tabs: Vec::new(),
             children: Vec::new(),
+            on_event: None,
+            reorderable: true,
+        }
+    }
+
+    pub fn tab(mut self, tab: Tab) -> Self {
+        self.tabs.push(tab);
+        self
+    }
+
+    pub fn tabs(mut self, tabs: impl IntoIterator<Item = Tab>) -> Self {
+        self.tabs.extend(tabs);
+        self
+    }
+
+    pub fn on_event(self, handler: impl Fn(&TabBarEvent, &mut Window, &mut App) + 'static) -> Self {
+        Self {
+            on_event: Some(Rc::new(handler)),
+            ..self
+        }
+    }
+
+    /// Whether tabs can be reordered by dragging them, enabled by default.
+    /// Owners implementing their own dragging turn it off.
+    pub fn reorderable(self, reorderable: bool) -> Self {
+        Self {
+            reorderable,
+            ..self
         }
     }
 }
 
 impl RenderOnce for TabBar {
-    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let scroll_handle = window
+            .use_keyed_state(self.id.clone(), cx, |_, _| ScrollHandle::new())
+            .read(cx)
+            .clone();
+        let selection = cx.theme().selection;
+        let pinned_count = self.tabs.iter().filter(|tab| tab.pinned).count();
+        let tab_count = self.tabs.len();
+
+        let mut pinned = Vec::new();
+        let mut unpinned = Vec::new();
+        for (ix, mut tab) in self.tabs.into_iter().enumerate() {
+            let is_pinned = tab.pinned;
+            if let Some(on_event) = self.on_event.clone() {
+                let emit = move |event: TabBarEvent, window: &mut Window, cx: &mut App| {
+                    on_event(&event, window, cx)
+                };
+                let emit = Rc::new(emit);
+                if !is_pinned && tab.on_close.is_none() {
+                    let emit = emit.clone();
+                    tab.on_close = Some(Rc::new(move |window, cx| {
+                        emit(TabBarEvent::Closed(ix), window, cx)
+                    }));
+                }
+                tab = tab.on_click({
+                    let emit = emit.clone();
+                    move |_, window, cx| emit(TabBarEvent::Activated(ix), window, cx)
+                });
+                if !is_pinned {
+                    tab = tab.on_mouse_down(MouseButton::Middle, {
+                        let emit = emit.clone();
+                        move |_, window, cx| {
+                            cx.stop_propagation();
+                            emit(TabBarEvent::Closed(ix), window, cx)
+                        }
+                    });
+                }
+                if self.reorderable {
+                    let bar = self.id.clone();
+                    let drop_bar = self.id.clone();
+                    // Tabs only move within their own group, pinned or not.
+                    let group = move |other: usize| (other < pinned_count) == is_pinned;
+                    tab = tab
+                        .on_drag(DraggedTab { bar, ix }, |tab, _, _, cx| {
+                            cx.new(|_| tab.clone())
+                        })
+                        .drag_over::<DraggedTab>(move |style, dragged, _, _| {
+                            if dragged.bar == drop_bar && group(dragged.ix) {
+                                style.border_l_2().border_color(selection)
+                            } else {
+                                style
+                            }
+                        })
+                        .on_drop({
+                            let bar = self.id.clone();
+                            move |dragged: &DraggedTab, window, cx| {
+                                if dragged.bar == bar && dragged.ix != ix && group(dragged.ix) {
+                                    emit(
+                                        TabBarEvent::Reordered {
+                                            from: dragged.ix,
+                                            to: ix,
+                                        },
+                                        window,
+                                        cx,
+                                    );
+                                }
+                            }
+                        });
+                }
+            }
+            if is_pinned {
+                pinned.push(tab);
+            } else {
+                unpinned.push(tab);
+            }
+        }
+
+        // Takes drops behind the last tab of the pinned or unpinned group.
+        let end_drop_target = |id: &'static str, to: usize, pinned: bool| {
+            div().id(id).h_full().when_some(
+                self.on_event.clone().filter(|_| self.reorderable),
+                |this, on_event| {
+                    let bar = self.id.clone();
+                    let drop_bar = self.id.clone();
+                    let group = move |other: usize| (other < pinned_count) == pinned;
+                    this.drag_over::<DraggedTab>(move |style, dragged, _, _| {
+                        if dragged.bar == drop_bar && group(dragged.ix) {
+                            style.border_l_2().border_color(selection)
+                        } else {
+                            style
+                        }
+                    })
+                    .on_drop(move |dragged: &DraggedTab, window, cx| {
+                        if dragged.bar == bar && group(dragged.ix) {
+                            on_event(
+                                &TabBarEvent::Reordered {
+                                    from: dragged.ix,
+                                    to,
+                                },
+                                window,
+                                cx,
+                            );
+                        }
+                    })
+                },
+            )
+        };
+        let pinned_end = (pinned_count > 0)
+            .then(|| end_drop_target("tab-bar-pinned-end", pinned_count, true).w_2());
+        let unpinned_end = end_drop_target("tab-bar-end", tab_count, false)
+            .flex_1()
+            .min_w_4();
+
+        let overflow = scroll_handle.max_offset().width > px(0.0);
+        let offset = scroll_handle.offset();
+        let scroll_button = |id: &'static str, icon: &'static str, enabled: bool, step: Pixels| {
+            let scroll_handle = scroll_handle.clone();
+            Button::new(id)
+                .flex_none()
+                .h_full()
+                .px_0p5()
+                .rounded(cx.theme().radii.sm)
+                .justify_center()
+                .when(enabled, |this| this.hover(selection))
+                .child(
+                    svg()
+                        .size_4()
+                        .text_color(if enabled {
+                            cx.theme().text
+                        } else {
+                            cx.theme().muted
+                        })
+                        .path(icon),
+                )
+                .on_click(move |_, window, _| {
+                    let max = scroll_handle.max_offset().width;
+                    let offset = scroll_handle.offset();
+                    let x = (offset.x + step).min(px(0.0)).max(-max);
+                    scroll_handle.set_offset(point(x, offset.y));
+                    window.refresh();
+                })
+        };
+
         h_flex()
             .id(self.id)
             .track_focus(&self.focus)
             .bg(cx.theme().foreground)
             .w_full()
-            .h_9()
+            .h(cx.theme().sizes.bar_height)
             .p_0p5()
             .gap_0p5()
             .items_center()
+            .children(pinned)
+            .children(pinned_end)
+            .when(overflow, |this| {
+                this.child(scroll_button(
+                    "tab-bar-scroll-left",
+                    "chevron_left",
+                    offset.x < px(0.0),
+                    SCROLL_STEP,
+                ))
+            })
+            .child(
+                h_flex()
+                    .id("tab-bar-tabs")
+                    .flex_1()
+                    .min_w_0()
+                    .h_full()
+                    .gap_0p5()
+                    .overflow_x_scroll()
+                    .track_scroll(&scroll_handle)
+                    .children(unpinned)
+                    .child(unpinned_end),
+            )
+            .when(overflow, |this| {
+                this.child(scroll_button(
+                    "tab-bar-scroll-right",
+                    "chevron_right",
+                    offset.x > -scroll_handle.max_offset().width,
+                    -SCROLL_STEP,
+                ))
+            })
             .children(self.children)
     }
 }
 
 #[derive(IntoElement)]
 pub struct Tab {
+    id: ElementId,
     base: Button,
     inner: AnyElement,
     selected: bool,
+    pinned: bool,
+    dirty: bool,
+    on_close: Option<Rc<dyn Fn(&mut Window, &mut App)>>,
 }
 
 impl Tab {
     pub fn new(id: impl Into<ElementId>, inner: impl IntoElement) -> Self {
+        let id = id.into();
         Self {
+            id: id.clone(),
             base: Button::new(id),
             inner: inner.into_any_element(),
             selected: false,
+            pinned: false,
+            dirty: false,
+            on_close: None,
         }
     }
 
     pub fn selected(self, selected: bool) -> Self {
         Self { selected, ..self }
     }
+
+    /// Pinned tabs stay in front of all others, don't scroll and can't be
+    /// closed.
+    pub fn pinned(self, pinned: bool) -> Self {
+        Self { pinned, ..self }
+    }
+
+    /// Marks unsaved changes with a dot, which turns into the close button
+    /// on hover.
+    pub fn dirty(self, dirty: bool) -> Self {
+        Self { dirty, ..self }
+    }
+
+    /// Shows a close button calling `handler`. Inside a [`TabBar`] with an
+    /// event handler the button emits [`TabBarEvent::Closed`] instead.
+    pub fn on_close(self, handler: impl Fn(&mut Window, &mut App) + 'static) -> Self {
+        Self {
+            on_close: Some(Rc::new(handler)),
+            ..self
+        }
+    }
 }
 
 impl InteractiveElement for Tab {
@@ -66,14 +338,75 @@ impl InteractiveElement for Tab {
     }
 }
 
+impl StatefulInteractiveElement for Tab {}
+
 impl RenderOnce for Tab {
     fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        let closable = self.on_close.is_some() && !self.pinned;
+        let style = cx.theme().tab();
+        let text = style.text;
+        // Its own id, as the fade state is keyed by it.
+        let close_id = ElementId::Name(format!("{}-close", self.id).into());
+
+        // The button fades between the base and hover color.
         self.base
-            .px_2()
+            .group("tab")
+            .flex_none()
+            .px(cx.theme().spacing.md)
             .h_full()
-            .bg(cx.theme().background)
-            .when(self.selected, |this| this.bg(cx.theme().selection))
-            .hover(cx.theme().selection)
-            .child(self.inner)
+            .justify_center()
+            .rounded(style.radius)
+            .text_color(text)
+            .base(if self.selected {
+                style.active
+            } else {
+                style.background
+            })
+            .hover(style.hover)
+            .child(h_flex().gap_1().items_center().child(self.inner).when(
+                closable || self.dirty,
+                |this| {
+                    this.child(
+                        div()
+                            .relative()
+                            .size_4()
+                            .flex_none()
+                            .when(self.dirty, |this| {
+                                this.child(
+                                    div()
+                                        .absolute()
+                                        .inset_1()
+                                        .rounded_full()
+                                        .bg(text)
+                                        .when(closable, |this| {
+                                            this.group_hover("tab", |style| style.invisible())
+                                        }),
+                                )
+                            })
+                            .when_some(self.on_close.filter(|_| closable), |this, on_close| {
+                                this.child(
+                                    Button::new(close_id)
+                                        .absolute()
+                                        .inset_0()
+                                        .rounded(cx.theme().radii.sm)
+                                        .when(!self.selected, |this| {
+                                            this.invisible()
+                                                .group_hover("tab", |style| style.visible())
+                                        })
+                                        .when(self.dirty, |this| {
+                                            this.invisible()
+                                                .group_hover("tab", |style| style.visible())
+                                        })
+                                        .hover(cx.theme().muted_background)
+                                        .child(svg().size_4().text_color(text).path("close"))
+                                        .on_mouse_down(MouseButton::Left, |_, _, cx| {
+                                            cx.stop_propagation()
+                                        })
+                                        .on_click(move |_, window, cx| on_close(window, cx)),
+                                )
+                            }),
+                    )
+                },
+            ))
     }
 }
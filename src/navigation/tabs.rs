@@ -0,0 +1,380 @@
+use crate::prelude::*;
+use gpui::{prelude::*, *};
+
+actions!(
+    tabs,
+    [
+        /// Activates the tab behind the active one, wrapping around.
+        ActivateNextTab,
+        /// Activates the tab in front of the active one, wrapping around.
+        ActivatePreviousTab
+    ]
+);
+
+/// Activates the tab at the index, or the last one if there are fewer tabs.
+#[derive(Debug, Clone, PartialEq, Action)]
+#[action(namespace = tabs, no_json)]
+pub struct ActivateTab(pub usize);
+
+pub fn init(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("ctrl-tab", ActivateNextTab, Some("Tabs")),
+        KeyBinding::new("ctrl-shift-tab", ActivatePreviousTab, Some("Tabs")),
+    ]);
+
+    let modifier = if cfg!(target_os = "macos") {
+        "cmd"
+    } else {
+        "ctrl"
+    };
+    cx.bind_keys(
+        (1..=9)
+            .map(|n| KeyBinding::new(&format!("{modifier}-{n}"), ActivateTab(n - 1), Some("Tabs"))),
+    );
+}
+
+type ContentBuilder = Box<dyn Fn(&mut Window, &mut App) -> AnyView>;
+
+pub struct TabItem {
+    title: SharedString,
+    view: Option<AnyView>,
+    builder: Option<ContentBuilder>,
+    pinned: bool,
+    dirty: bool,
+}
+
+impl TabItem {
+    pub fn new(title: impl Into<SharedString>, view: impl Into<AnyView>) -> Self {
+        Self {
+            title: title.into(),
+            view: Some(view.into()),
+            builder: None,
+            pinned: false,
+            dirty: false,
+        }
+    }
+
+    /// A tab whose content is only built once it gets activated.
+    pub fn lazy<V: Render>(
+        title: impl Into<SharedString>,
+        builder: impl Fn(&mut Window, &mut App) -> Entity<V> + 'static,
+    ) -> Self {
+        Self {
+            title: title.into(),
+            view: None,
+            builder: Some(Box::new(move |window, cx| builder(window, cx).into())),
+            pinned: false,
+            dirty: false,
+        }
+    }
+
+    pub fn pinned(self, pinned: bool) -> Self {
+        Self { pinned, ..self }
+    }
+
+    pub fn dirty(self, dirty: bool) -> Self {
+        Self { dirty, ..self }
+    }
+}
+
+/// Tab bar together with the content of the active tab.
+///
+/// Emits the [`TabBarEvent`]s after applying them, so the owner can follow
+/// along with its own model.
+pub struct Tabs {
+    focus: FocusHandle,
+    items: Vec<TabItem>,
+    active: usize,
+    keep_alive: bool,
+}
+
+impl EventEmitter<TabBarEvent> for Tabs {}
+
+impl Tabs {
+    pub fn new(items: impl IntoIterator<Item = TabItem>, cx: &mut Context<Self>) -> Self {
+        let mut this = Self {
+            focus: cx.focus_handle(),
+            items: Vec::new(),
+            active: 0,
+            keep_alive: true,
+        };
+        for item in items {
+            this.insert(item);
+        }
+        this
+    }
+
+    /// Whether lazily built contents are kept when their tab gets inactive,
+    /// enabled by default. Otherwise they are built again on activation.
+    pub fn keep_alive(self, keep_alive: bool) -> Self {
+        Self { keep_alive, ..self }
+    }
+
+    /// Inserts behind the other tabs of the same group, pinned tabs always
+    /// staying in front. Returns the index of the new tab.
+    fn insert(&mut self, item: TabItem) -> usize {
+        let ix = if item.pinned {
+            self.items.iter().take_while(|item| item.pinned).count()
+        } else {
+            self.items.len()
+        };
+        self.items.insert(ix, item);
+        if ix <= self.active && self.items.len() > 1 {
+            self.active += 1;
+        }
+        ix
+    }
+
+    pub fn add_tab(&mut self, item: TabItem, cx: &mut Context<Self>) -> usize {
+        let ix = self.insert(item);
+        cx.notify();
+        ix
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn active_index(&self) -> usize {
+        self.active
+    }
+
+    pub fn activate(&mut self, ix: usize, cx: &mut Context<Self>) {
+        let ix = ix.min(self.items.len().saturating_sub(1));
+        if ix == self.active {
+            return;
+        }
+        if !self.keep_alive
+            && let Some(item) = self.items.get_mut(self.active)
+            && item.builder.is_some()
+        {
+            item.view = None;
+        }
+        self.active = ix;
+        cx.emit(TabBarEvent::Activated(ix));
+        cx.notify();
+    }
+
+    pub fn close(&mut self, ix: usize, cx: &mut Context<Self>) {
+        if self.items.get(ix).is_none_or(|item| item.pinned) {
+            return;
+        }
+        self.items.remove(ix);
+        if self.active > ix || self.active >= self.items.len() {
+            self.active = self.active.saturating_sub(1);
+        }
+        cx.emit(TabBarEvent::Closed(ix));
+        cx.notify();
+    }
+
+    pub fn set_dirty(&mut self, ix: usize, dirty: bool, cx: &mut Context<Self>) {
+        if let Some(item) = self.items.get_mut(ix) {
+            item.dirty = dirty;
+            cx.notify();
+        }
+    }
+
+    fn reorder(&mut self, from: usize, to: usize, cx: &mut Context<Self>) {
+        if from >= self.items.len() || to > self.items.len() {
+            return;
+        }
+        // Dropping onto a tab inserts in front of it, which is one further
+        // left once the dragged tab was taken out in front of it.
+        let target = if from < to { to - 1 } else { to };
+        if target == from {
+            return;
+        }
+        let active = self.active;
+        let item = self.items.remove(from);
+        self.items.insert(target, item);
+        self.active = if active == from {
+            target
+        } else if from < active && target >= active {
+            active - 1
+        } else if from > active && target <= active {
+            active + 1
+        } else {
+            active
+        };
+        cx.emit(TabBarEvent::Reordered { from, to });
+        cx.notify();
+    }
+
+    fn handle_tab_bar_event(
+        &mut self,
+        event: &TabBarEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match *event {
+            TabBarEvent::Activated(ix) => {
+                self.activate(ix, cx);
+                window.focus(&self.focus);
+            }
+            TabBarEvent::Closed(ix) => self.close(ix, cx),
+            TabBarEvent::Reordered { from, to } => self.reorder(from, to, cx),
+        }
+    }
+
+    fn activate_next(&mut self, _: &ActivateNextTab, _: &mut Window, cx: &mut Context<Self>) {
+        if !self.items.is_empty() {
+            self.activate((self.active + 1) % self.items.len(), cx);
+        }
+    }
+
+    fn activate_previous(
+        &mut self,
+        _: &ActivatePreviousTab,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.items.is_empty() {
+            let len = self.items.len();
+            self.activate((self.active + len - 1) % len, cx);
+        }
+    }
+
+    fn activate_tab(&mut self, action: &ActivateTab, _: &mut Window, cx: &mut Context<Self>) {
+        self.activate(action.0, cx);
+    }
+}
+
+impl Focusable for Tabs {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus.clone()
+    }
+}
+
+impl Render for Tabs {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if let Some(item) = self.items.get_mut(self.active)
+            && item.view.is_none()
+            && let Some(builder) = item.builder.as_ref()
+        {
+            item.view = Some(builder(window, cx));
+        }
+        let content = self
+            .items
+            .get(self.active)
+            .and_then(|item| item.view.clone());
+
+        let tabs = self.items.iter().enumerate().map(|(ix, item)| {
+            Tab::new(("tab", ix), item.title.clone())
+                .selected(ix == self.active)
+                .pinned(item.pinned)
+                .dirty(item.dirty)
+        });
+
+        v_flex()
+            .key_context("Tabs")
+            .size_full()
+            .on_action(cx.listener(Self::activate_next))
+            .on_action(cx.listener(Self::activate_previous))
+            .on_action(cx.listener(Self::activate_tab))
+            .child(
+                TabBar::new(("tabs", cx.entity_id()), self.focus.clone())
+                    .tabs(tabs)
+                    .on_event(cx.listener(Self::handle_tab_bar_event)),
+            )
+            .child(div().flex_1().overflow_hidden().children(content))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Blank;
+
+    impl Render for Blank {
+        fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+            Empty
+        }
+    }
+
+    fn item(title: &'static str, cx: &mut App) -> TabItem {
+        TabItem::new(title, cx.new(|_| Blank))
+    }
+
+    /// Titles in order, with the active one in brackets.
+    fn titles(tabs: &Tabs) -> Vec<String> {
+        tabs.items
+            .iter()
+            .enumerate()
+            .map(|(ix, item)| {
+                if ix == tabs.active {
+                    format!("[{}]", item.title)
+                } else {
+                    item.title.to_string()
+                }
+            })
+            .collect()
+    }
+
+    fn tabs(cx: &mut TestAppContext) -> Entity<Tabs> {
+        cx.update(|cx| {
+            let items = [
+                item("a", cx),
+                item("pinned", cx).pinned(true),
+                item("b", cx),
+                item("c", cx),
+            ];
+            cx.new(|cx| Tabs::new(items, cx))
+        })
+    }
+
+    #[gpui::test]
+    fn insert_keeps_the_active_tab(cx: &mut TestAppContext) {
+        let tabs = tabs(cx);
+        tabs.update(cx, |tabs, cx| {
+            assert_eq!(titles(tabs), ["pinned", "[a]", "b", "c"]);
+            tabs.activate(2, cx);
+            let pinned = item("also pinned", cx).pinned(true);
+            assert_eq!(tabs.add_tab(pinned, cx), 1);
+            let last = item("d", cx);
+            assert_eq!(tabs.add_tab(last, cx), 5);
+            assert_eq!(
+                titles(tabs),
+                ["pinned", "also pinned", "a", "[b]", "c", "d"]
+            );
+        });
+    }
+
+    #[gpui::test]
+    fn close_moves_to_the_neighbouring_tab(cx: &mut TestAppContext) {
+        let tabs = tabs(cx);
+        tabs.update(cx, |tabs, cx| {
+            tabs.activate(2, cx);
+            tabs.close(1, cx);
+            assert_eq!(titles(tabs), ["pinned", "[b]", "c"]);
+            tabs.close(0, cx);
+            assert_eq!(titles(tabs), ["pinned", "[b]", "c"]);
+            tabs.close(1, cx);
+            assert_eq!(titles(tabs), ["pinned", "[c]"]);
+            tabs.close(1, cx);
+            assert_eq!(titles(tabs), ["[pinned]"]);
+        });
+    }
+
+    #[gpui::test]
+    fn reorder_follows_the_active_tab(cx: &mut TestAppContext) {
+        let tabs = tabs(cx);
+        tabs.update(cx, |tabs, cx| {
+            tabs.reorder(1, 4, cx);
+            assert_eq!(titles(tabs), ["pinned", "b", "c", "[a]"]);
+            tabs.reorder(3, 1, cx);
+            assert_eq!(titles(tabs), ["pinned", "[a]", "b", "c"]);
+            tabs.reorder(3, 1, cx);
+            assert_eq!(titles(tabs), ["pinned", "c", "[a]", "b"]);
+            tabs.reorder(1, 4, cx);
+            assert_eq!(titles(tabs), ["pinned", "[a]", "b", "c"]);
+            // Dropping a tab in front of itself or the tab behind it is a no-op.
+            tabs.reorder(1, 2, cx);
+            assert_eq!(titles(tabs), ["pinned", "[a]", "b", "c"]);
+        });
+    }
+}
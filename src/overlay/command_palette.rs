@@ -0,0 +1,329 @@
+use std::collections::HashSet;
+
+use crate::overlay::menu_builder::Cancel;
+use crate::{fuzzy, input::EnrichedInputAction, prelude::*};
+use gpui::{prelude::*, *};
+
+actions!(
+    command_palette,
+    [
+        /// Opens the command palette.
+        Toggle
+    ]
+);
+
+/// Number of recently used commands remembered for ordering the palette.
+const MAX_HISTORY: usize = 20;
+
+pub fn init(cx: &mut App) {
+    cx.bind_keys([KeyBinding::new("escape", Cancel, Some("CommandPalette"))]);
+
+    let toggle = if cfg!(target_os = "macos") {
+        "cmd-shift-p"
+    } else {
+        "ctrl-shift-p"
+    };
+    cx.bind_keys([KeyBinding::new(toggle, Toggle, None)]);
+}
+
+#[derive(Default)]
+struct CommandHistory {
+    recent: Vec<&'static str>,
+}
+
+impl Global for CommandHistory {}
+
+struct Command {
+    name: &'static str,
+    label: SharedString,
+    action: Box<dyn Action>,
+    keybinding: Option<SharedString>,
+}
+
+/// Turns `text_input::SelectAll` into `text input: select all`.
+fn humanize_action_name(name: &str) -> String {
+    let mut result = String::with_capacity(name.len() + 8);
+    let mut previous = None;
+    let mut chars = name.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            ':' if chars.peek() == Some(&':') => {
+                chars.next();
+                result.push_str(": ");
+            }
+            '_' => result.push(' '),
+            c if c.is_uppercase() => {
+                if previous.is_some_and(|p: char| p.is_lowercase() || p.is_ascii_digit()) {
+                    result.push(' ');
+                }
+                result.extend(c.to_lowercase());
+            }
+            c => result.push(c),
+        }
+        previous = Some(c);
+    }
+    result
+}
+
+/// Modal list of the actions available where the focus was when the palette
+/// got opened. The chosen action is dispatched back to that element.
+///
+/// The palette positions itself, so the owning view only has to render the
+/// entity while it is open and drop it on `DismissEvent`, e.g. in response to
+/// the [`Toggle`] action.
+pub struct CommandPalette {
+    input: Entity<TextInput>,
+    previous_focus: Option<FocusHandle>,
+    commands: Vec<Command>,
+    /// Indices into `commands` with the matched label positions.
+    matches: Vec<(usize, Vec<usize>)>,
+    selected: usize,
+    scroll_handle: ScrollHandle,
+    _subscription: Subscription,
+}
+
+impl CommandPalette {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let previous_focus = window.focused(cx);
+        let mut seen = HashSet::new();
+        let commands = window
+            .available_actions(cx)
+            .into_iter()
+            .filter(|action| action.name() != Toggle.name() && seen.insert(action.name()))
+            .map(|action| Command {
+                name: action.name(),
+                label: humanize_action_name(action.name()).into(),
+                keybinding: keybinding_text(action.as_ref(), previous_focus.as_ref(), window),
+                action,
+            })
+            .collect();
+
+        let palette = cx.weak_entity();
+        let input = cx.new(|cx| {
+            TextInput::new(
+                cx.focus_handle(),
+                None,
+                Some("Execute a command...".into()),
+                Some(Box::new(move |action, window, cx| {
+                    _ = palette.update(cx, |this, cx| match action {
+                        EnrichedInputAction::Next => this.select_offset(1, cx),
+                        EnrichedInputAction::Previous => this.select_offset(-1, cx),
+                        EnrichedInputAction::Accept => this.confirm(window, cx),
+                    });
+                })),
+            )
+        });
+        let subscription = cx.subscribe(&input, |this, _, query: &String, cx| {
+            this.update_matches(query, cx);
+        });
+        window.focus(&input.focus_handle(cx));
+
+        let mut this = Self {
+            input,
+            previous_focus,
+            commands,
+            matches: Vec::new(),
+            selected: 0,
+            scroll_handle: ScrollHandle::new(),
+            _subscription: subscription,
+        };
+        this.update_matches("", cx);
+        this
+    }
+
+    pub fn build(window: &mut Window, cx: &mut App) -> Entity<Self> {
+        cx.new(|cx| Self::new(window, cx))
+    }
+
+    fn update_matches(&mut self, query: &str, cx: &mut Context<Self>) {
+        let recent = cx
+            .try_global::<CommandHistory>()
+            .map(|history| history.recent.as_slice())
+            .unwrap_or_default();
+        let recency = |name: &str| {
+            recent
+                .iter()
+                .position(|recent| *recent == name)
+                .unwrap_or(usize::MAX)
+        };
+
+        let mut matches = self
+            .commands
+            .iter()
+            .enumerate()
+            .filter_map(|(ix, command)| {
+                let m = fuzzy::fuzzy_match(query, &command.label)?;
+                Some((ix, m))
+            })
+            .collect::<Vec<_>>();
+        if query.is_empty() {
+            matches.sort_by(|(a, _), (b, _)| {
+                let (a, b) = (&self.commands[*a], &self.commands[*b]);
+                recency(a.name)
+                    .cmp(&recency(b.name))
+                    .then_with(|| a.label.cmp(&b.label))
+            });
+        } else {
+            matches.sort_by(|(a, a_match), (b, b_match)| {
+                b_match.score.cmp(&a_match.score).then_with(|| {
+                    recency(self.commands[*a].name).cmp(&recency(self.commands[*b].name))
+                })
+            });
+        }
+
+        self.matches = matches
+            .into_iter()
+            .map(|(ix, m)| (ix, m.positions))
+            .collect();
+        self.selected = 0;
+        self.scroll_handle.scroll_to_item(0);
+        cx.notify();
+    }
+
+    fn select_offset(&mut self, offset: isize, cx: &mut Context<Self>) {
+        if self.matches.is_empty() {
+            return;
+        }
+        let len = self.matches.len() as isize;
+        self.selected = (self.selected as isize + offset).rem_euclid(len) as usize;
+        self.scroll_handle.scroll_to_item(self.selected);
+        cx.notify();
+    }
+
+    fn confirm(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some((command_ix, _)) = self.matches.get(self.selected) else {
+            return;
+        };
+        let command = &self.commands[*command_ix];
+        let action = command.action.boxed_clone();
+        let name = command.name;
+
+        let history = cx.default_global::<CommandHistory>();
+        history.recent.retain(|recent| *recent != name);
+        history.recent.insert(0, name);
+        history.recent.truncate(MAX_HISTORY);
+
+        let previous_focus = self.previous_focus.clone();
+        self.dismiss(window, cx);
+        window.defer(cx, move |window, cx| match previous_focus {
+            Some(focus) => focus.dispatch_action(action.as_ref(), window, cx),
+            None => window.dispatch_action(action, cx),
+        });
+    }
+
+    fn dismiss(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(previous_focus) = self.previous_focus.as_ref() {
+            window.focus(previous_focus);
+        }
+        cx.emit(DismissEvent);
+    }
+}
+
+impl Focusable for CommandPalette {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.input.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for CommandPalette {}
+
+impl Render for CommandPalette {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let width = px(560.0);
+        let position = point((window.viewport_size().width - width) / 2.0, px(64.0));
+        let selection = cx.theme().selection;
+        let hint = cx.theme().hint;
+        let muted = cx.theme().muted;
+
+        deferred(
+            anchored()
+                .position(position)
+                .snap_to_window_with_margin(px(8.0))
+                .child(
+                    v_flex()
+                        .id("command-palette")
+                        .occlude()
+                        .key_context("CommandPalette")
+                        .w(width)
+                        .bg(cx.theme().foreground)
+                        .border_1()
+                        .border_color(cx.theme().border)
+                        .rounded(cx.theme().radii.lg)
+                        .shadow(box_shadows(&cx.theme().shadows.lg))
+                        .text_color(cx.theme().text)
+                        .on_action(
+                            cx.listener(|this, _: &Cancel, window, cx| this.dismiss(window, cx)),
+                        )
+                        .on_mouse_down_out(
+                            cx.listener(|this, _, window, cx| this.dismiss(window, cx)),
+                        )
+                        .child(
+                            div()
+                                .px(cx.theme().spacing.md)
+                                .py_1()
+                                .border_b_1()
+                                .border_color(cx.theme().border)
+                                .child(self.input.clone()),
+                        )
+                        .child(
+                            v_flex()
+                                .id("command-palette-list")
+                                .max_h(px(320.0))
+                                .overflow_y_scroll()
+                                .track_scroll(&self.scroll_handle)
+                                .p_1()
+                                .gap_0p5()
+                                .when(self.matches.is_empty(), |this| {
+                                    this.child(
+                                        div()
+                                            .px(cx.theme().spacing.md)
+                                            .py_0p5()
+                                            .text_color(muted)
+                                            .child("No matching commands"),
+                                    )
+                                })
+                                .children(self.matches.iter().enumerate().map(
+                                    |(ix, (command_ix, positions))| {
+                                        let command = &self.commands[*command_ix];
+                                        Button::new(("command-palette-item", ix))
+                                            .hover(selection)
+                                            .when(ix == self.selected, |this| this.base(selection))
+                                            .child(
+                                                h_flex()
+                                                    .px(cx.theme().spacing.md)
+                                                    .py_0p5()
+                                                    .gap_4()
+                                                    .justify_between()
+                                                    .items_center()
+                                                    .child(fuzzy::highlighted_text(
+                                                        command.label.clone(),
+                                                        positions,
+                                                        hint,
+                                                    ))
+                                                    .when_some(
+                                                        command.keybinding.clone(),
+                                                        |this, keybinding| {
+                                                            this.child(
+                                                                div()
+                                                                    .flex_none()
+                                                                    .text_color(muted)
+                                                                    .child(keybinding),
+                                                            )
+                                                        },
+                                                    ),
+                                            )
+                                            .on_mouse_down(
+                                                MouseButton::Left,
+                                                cx.listener(move |this, _, window, cx| {
+                                                    this.selected = ix;
+                                                    this.confirm(window, cx);
+                                                }),
+                                            )
+                                    },
+                                )),
+                        ),
+                ),
+        )
+        .with_priority(2)
+    }
+}
@@ -1,6 +1,12 @@
-use std::rc::Rc;
+use std::{
+    cell::{Cell, RefCell},
+    cmp::Reverse,
+    collections::HashMap,
+    rc::Rc,
+    time::{Duration, Instant},
+};
 
-use crate::prelude::*;
+use crate::{fuzzy, input::EnrichedInputAction, prelude::*};
 use gpui::{prelude::*, *};
 
 actions!(
@@ -20,29 +26,261 @@ actions!(
         SelectFirst,
         /// Selects the last item in the menu.
         SelectLast,
+        /// Opens the submenu of the selected item.
+        SelectChild,
+        /// Closes the submenu and returns to its parent menu.
+        SelectParent,
         /// Restarts the menu from the beginning.
         Restart,
         EndSlot,
     ]
 );
 
+/// How long a hover over a sibling item is held back while the mouse is
+/// travelling towards an open submenu.
+const SUBMENU_AIM_TIMEOUT: Duration = Duration::from_millis(300);
+/// Pause after which type-ahead starts a new search instead of extending it.
+const TYPE_AHEAD_TIMEOUT: Duration = Duration::from_millis(1000);
+
+pub fn init(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("escape", Cancel, Some("menu")),
+        KeyBinding::new("enter", Confirm, Some("menu")),
+        KeyBinding::new("up", SelectPrevious, Some("menu")),
+        KeyBinding::new("down", SelectNext, Some("menu")),
+        KeyBinding::new("home", SelectFirst, Some("menu")),
+        KeyBinding::new("end", SelectLast, Some("menu")),
+        KeyBinding::new("right", SelectChild, Some("menu")),
+        KeyBinding::new("left", SelectParent, Some("menu")),
+    ]);
+}
+
+pub type SubmenuBuilder =
+    Rc<dyn Fn(MenuBuilder, &mut Window, &mut Context<MenuBuilder>) -> MenuBuilder>;
+
 pub enum MenuListItem {
     NonInteractive(Box<dyn Fn(&mut Window, &mut App) -> AnyElement>),
     InteractiveElement {
         render: Box<dyn Fn(&mut Window, &mut App) -> AnyElement>,
         handler: Option<Rc<dyn Fn(&mut Window, &mut App)>>,
+        label: Option<SharedString>,
+    },
+    Submenu {
+        render: Box<dyn Fn(&mut Window, &mut App) -> AnyElement>,
+        builder: SubmenuBuilder,
+        label: Option<SharedString>,
     },
+    Entry(MenuEntry),
+    Separator,
+    Header(SharedString),
+}
+
+impl MenuListItem {
+    /// Text the item is matched against when filtering or jumping to items
+    /// by typing.
+    pub fn label(&self) -> Option<&SharedString> {
+        match self {
+            MenuListItem::InteractiveElement { label, .. }
+            | MenuListItem::Submenu { label, .. } => label.as_ref(),
+            MenuListItem::Entry(entry) => Some(&entry.label),
+            MenuListItem::NonInteractive(_) | MenuListItem::Separator | MenuListItem::Header(_) => {
+                None
+            }
+        }
+    }
+
+    fn is_selectable(&self) -> bool {
+        match self {
+            MenuListItem::InteractiveElement { .. } | MenuListItem::Submenu { .. } => true,
+            MenuListItem::Entry(entry) => !entry.disabled,
+            MenuListItem::NonInteractive(_) | MenuListItem::Separator | MenuListItem::Header(_) => {
+                false
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuToggle {
+    Check(bool),
+    Radio(bool),
+}
+
+/// A menu item rendered by the menu itself from a label and optional
+/// decorations, as opposed to the free-form `interactive` items.
+pub struct MenuEntry {
+    label: SharedString,
+    icon: Option<SharedString>,
+    toggle: Option<MenuToggle>,
+    disabled: bool,
+    action: Option<Box<dyn Action>>,
+    shortcut: Option<SharedString>,
+    handler: Option<Rc<dyn Fn(&mut Window, &mut App)>>,
+}
+
+impl MenuEntry {
+    pub fn new(label: impl Into<SharedString>) -> Self {
+        Self {
+            label: label.into(),
+            icon: None,
+            toggle: None,
+            disabled: false,
+            action: None,
+            shortcut: None,
+            handler: None,
+        }
+    }
+
+    /// Svg asset path of the icon shown in front of the label.
+    pub fn icon(self, path: impl Into<SharedString>) -> Self {
+        Self {
+            icon: Some(path.into()),
+            ..self
+        }
+    }
+
+    pub fn toggle(self, toggle: MenuToggle) -> Self {
+        Self {
+            toggle: Some(toggle),
+            ..self
+        }
+    }
+
+    pub fn disabled(self, disabled: bool) -> Self {
+        Self { disabled, ..self }
+    }
+
+    /// Dispatches `action` when the entry is confirmed. The key binding of the
+    /// action is shown right aligned next to the label.
+    pub fn action(self, action: Box<dyn Action>) -> Self {
+        Self {
+            action: Some(action),
+            ..self
+        }
+    }
+
+    /// Shows `shortcut` instead of the key binding of the entry's action.
+    pub fn shortcut(self, shortcut: impl Into<SharedString>) -> Self {
+        Self {
+            shortcut: Some(shortcut.into()),
+            ..self
+        }
+    }
+
+    pub fn handler(self, handler: impl Fn(&mut Window, &mut App) + 'static) -> Self {
+        Self {
+            handler: Some(Rc::new(handler)),
+            ..self
+        }
+    }
+
+    fn render(
+        &self,
+        action_context: Option<&FocusHandle>,
+        highlights: Option<&[usize]>,
+        window: &Window,
+        cx: &App,
+    ) -> AnyElement {
+        let text_color = if self.disabled {
+            cx.theme().muted
+        } else {
+            cx.theme().menu().text
+        };
+        let shortcut = self.shortcut.clone().or_else(|| {
+            self.action
+                .as_ref()
+                .and_then(|action| keybinding_text(action.as_ref(), action_context, window))
+        });
+
+        h_flex()
+            .w_full()
+            .gap_2()
+            .px(cx.theme().spacing.md)
+            .py_0p5()
+            .items_center()
+            .text_color(text_color)
+            .when_some(self.toggle, |this, toggle| {
+                this.child(
+                    h_flex()
+                        .size_4()
+                        .flex_none()
+                        .items_center()
+                        .justify_center()
+                        .map(|this| match toggle {
+                            MenuToggle::Check(true) => this.child(
+                                svg()
+                                    .text_color(text_color)
+                                    .size_full()
+                                    .path("checkbox-inner"),
+                            ),
+                            MenuToggle::Radio(true) => {
+                                this.child(div().size_1p5().rounded_full().bg(text_color))
+                            }
+                            MenuToggle::Check(false) | MenuToggle::Radio(false) => this,
+                        }),
+                )
+            })
+            .when_some(self.icon.clone(), |this, icon| {
+                this.child(svg().text_color(text_color).flex_none().size_4().path(icon))
+            })
+            .child(div().flex_1().map(|this| match highlights {
+                Some(positions) => this.child(fuzzy::highlighted_text(
+                    self.label.clone(),
+                    positions,
+                    cx.theme().hint,
+                )),
+                None => this.child(self.label.clone()),
+            }))
+            .when_some(shortcut, |this, shortcut| {
+                this.child(
+                    div()
+                        .ml_4()
+                        .flex_none()
+                        .text_color(cx.theme().muted)
+                        .when_some(
+                            cx.theme().typography.mono_font_family.clone(),
+                            |this, family| this.font_family(family),
+                        )
+                        .child(shortcut),
+                )
+            })
+            .into_any_element()
+    }
+}
+
+struct OpenSubmenu {
+    index: usize,
+    menu: Entity<MenuBuilder>,
+    _subscription: Subscription,
 }
 
 pub struct MenuBuilder {
     //builder: Option<Rc<dyn Fn(Self, &mut Window, &mut Context<Self>) -> Self>>,
     items: Vec<MenuListItem>,
     focus: FocusHandle,
+    selected_index: Option<usize>,
     delayed: bool,
     keep_open_on_confirm: bool,
     fixed_width: Option<DefiniteLength>,
     interactive_accent_color: Option<Hsla>,
     rounding: AbsoluteLength,
+    action_context: Option<FocusHandle>,
+    depth: usize,
+    submenu: Option<OpenSubmenu>,
+    pending_hover: Option<usize>,
+    last_mouse_position: Option<Point<Pixels>>,
+    _aim_task: Option<Task<()>>,
+    filter: Option<Entity<TextInput>>,
+    query: String,
+    matches: HashMap<usize, Vec<usize>>,
+    type_ahead: String,
+    type_ahead_at: Option<Instant>,
+    _subscriptions: Vec<Subscription>,
+    bounds: Rc<Cell<Option<Bounds<Pixels>>>>,
+    item_bounds: Rc<RefCell<HashMap<usize, Bounds<Pixels>>>>,
+    /// Fades the menu in when opened and out when cancelled.
+    opacity: Transition<f32>,
+    close_task: Option<Task<()>>,
 }
 
 impl MenuBuilder {
@@ -52,13 +290,15 @@ impl MenuBuilder {
         builder: impl Fn(Self, &mut Window, &mut Context<Self>) -> Self,
     ) -> Self {
         let focus = cx.focus_handle();
+        let mut opacity = Transition::new(0.0);
+        opacity.set_target(1.0, cx);
         builder(
             Self {
                 //builder: None,
                 items: Vec::new(),
                 focus,
-                //action_context: None,
-                //selected_index: None,
+                action_context: None,
+                selected_index: None,
                 delayed: false,
                 //clicked: false,
                 //end_slot_action: None,
@@ -66,6 +306,21 @@ impl MenuBuilder {
                 fixed_width: None,
                 interactive_accent_color: None,
                 rounding: AbsoluteLength::Pixels(px(0.0)),
+                depth: 0,
+                submenu: None,
+                pending_hover: None,
+                last_mouse_position: None,
+                _aim_task: None,
+                filter: None,
+                query: String::new(),
+                matches: HashMap::new(),
+                type_ahead: String::new(),
+                type_ahead_at: None,
+                _subscriptions: Vec::new(),
+                bounds: Default::default(),
+                item_bounds: Default::default(),
+                opacity,
+                close_task: None,
             },
             window,
             cx,
@@ -96,7 +351,138 @@ impl MenuBuilder {
         self.items.push(MenuListItem::InteractiveElement {
             render: Box::new(render),
             handler: Some(Rc::new(handler)),
+            label: None,
+        });
+        self
+    }
+
+    /// Adds an item that opens a nested menu next to this one when hovered
+    /// or when it is selected and the `SelectChild` action is dispatched.
+    pub fn submenu(
+        mut self,
+        render: impl Fn(&mut Window, &mut App) -> AnyElement + 'static,
+        builder: impl Fn(Self, &mut Window, &mut Context<Self>) -> Self + 'static,
+    ) -> Self {
+        self.items.push(MenuListItem::Submenu {
+            render: Box::new(render),
+            builder: Rc::new(builder),
+            label: None,
+        });
+        self
+    }
+
+    /// Sets the text the previously added `interactive` or `submenu` item is
+    /// matched against when filtering or jumping to items by typing.
+    pub fn search_label(mut self, search_label: impl Into<SharedString>) -> Self {
+        if let Some(
+            MenuListItem::InteractiveElement { label, .. } | MenuListItem::Submenu { label, .. },
+        ) = self.items.last_mut()
+        {
+            *label = Some(search_label.into());
+        }
+        self
+    }
+
+    /// Shows a filter field at the top of the menu which narrows the items
+    /// down to those whose label fuzzy matches the entered text. Without it,
+    /// typing jumps to the best matching item instead.
+    pub fn filterable(
+        mut self,
+        placeholder: impl Into<SharedString>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let menu = cx.weak_entity();
+        let filter = cx.new(|cx| {
+            TextInput::new(
+                cx.focus_handle(),
+                None,
+                Some(placeholder.into()),
+                Some(Box::new(move |action, window, cx| {
+                    _ = menu.update(cx, |menu, cx| match action {
+                        EnrichedInputAction::Next => menu.select_next(&SelectNext, window, cx),
+                        EnrichedInputAction::Previous => {
+                            menu.select_previous(&SelectPrevious, window, cx)
+                        }
+                        EnrichedInputAction::Accept => menu.confirm(&Confirm, window, cx),
+                    });
+                })),
+            )
         });
+        self._subscriptions.push(cx.subscribe_in(
+            &filter,
+            window,
+            |this, _, query: &String, window, cx| this.set_query(query.clone(), window, cx),
+        ));
+        // keep the keyboard focus in the filter field while the menu is open
+        self._subscriptions
+            .push(cx.on_focus(&self.focus, window, |this, window, cx| {
+                if let Some(filter) = this.filter.as_ref() {
+                    window.focus(&filter.focus_handle(cx));
+                }
+            }));
+        self.filter = Some(filter);
+        self
+    }
+
+    pub fn item(mut self, entry: MenuEntry) -> Self {
+        self.items.push(MenuListItem::Entry(entry));
+        self
+    }
+
+    pub fn entry(
+        self,
+        label: impl Into<SharedString>,
+        handler: impl Fn(&mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.item(MenuEntry::new(label).handler(handler))
+    }
+
+    pub fn action(self, label: impl Into<SharedString>, action: Box<dyn Action>) -> Self {
+        self.item(MenuEntry::new(label).action(action))
+    }
+
+    pub fn checkable(
+        self,
+        label: impl Into<SharedString>,
+        checked: bool,
+        handler: impl Fn(&mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.item(
+            MenuEntry::new(label)
+                .toggle(MenuToggle::Check(checked))
+                .handler(handler),
+        )
+    }
+
+    pub fn radio(
+        self,
+        label: impl Into<SharedString>,
+        selected: bool,
+        handler: impl Fn(&mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.item(
+            MenuEntry::new(label)
+                .toggle(MenuToggle::Radio(selected))
+                .handler(handler),
+        )
+    }
+
+    pub fn separator(mut self) -> Self {
+        self.items.push(MenuListItem::Separator);
+        self
+    }
+
+    pub fn header(mut self, label: impl Into<SharedString>) -> Self {
+        self.items.push(MenuListItem::Header(label.into()));
+        self
+    }
+
+    /// Element that actions of the menu entries are dispatched to and whose
+    /// key bindings are shown. Defaults to whatever is focused once the menu
+    /// has been dismissed.
+    pub fn context(mut self, focus: FocusHandle) -> Self {
+        self.action_context = Some(focus);
         self
     }
 
@@ -106,16 +492,399 @@ impl MenuBuilder {
     }
 
     fn cancel(&mut self, _: &Cancel, _: &mut Window, cx: &mut Context<Self>) {
-        cx.emit(DismissEvent);
+        self.close_submenu(cx);
+        self.fade_out(cx);
+    }
+
+    /// Dismisses the menu once it faded out. Activating an item dismisses
+    /// right away instead, to have focus back before the item runs.
+    fn fade_out(&mut self, cx: &mut Context<Self>) {
+        if self.close_task.is_some() {
+            return;
+        }
+        self.opacity.set_target(0.0, cx);
+        if !self.opacity.is_animating() {
+            cx.emit(DismissEvent);
+            return;
+        }
+        self.close_task = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor()
+                .timer(animation::FADE_DURATION)
+                .await;
+            _ = this.update(cx, |this, cx| {
+                this.close_task = None;
+                cx.emit(DismissEvent);
+            });
+        }));
+        cx.notify();
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        match self.selected_index {
+            Some(ix) => self.activate(ix, window, cx),
+            None if !self.keep_open_on_confirm => self.fade_out(cx),
+            None => {}
+        }
+    }
+
+    fn is_navigable(&self, ix: usize) -> bool {
+        let visible = self.query.is_empty() || self.matches.contains_key(&ix);
+        visible && self.items[ix].is_selectable()
+    }
+
+    fn set_query(&mut self, query: String, window: &mut Window, cx: &mut Context<Self>) {
+        self.matches.clear();
+        if !query.is_empty() {
+            for (ix, item) in self.items.iter().enumerate() {
+                if let Some(label) = item.label()
+                    && let Some(m) = fuzzy::fuzzy_match(&query, label)
+                {
+                    self.matches.insert(ix, m.positions);
+                }
+            }
+        }
+        self.query = query;
+        let first = (0..self.items.len()).find(|ix| self.is_navigable(*ix));
+        self.select(first, window, cx);
+    }
+
+    fn type_ahead(&mut self, event: &KeyDownEvent, window: &mut Window, cx: &mut Context<Self>) {
+        let modifiers = event.keystroke.modifiers;
+        if self.filter.is_some() || modifiers.control || modifiers.alt || modifiers.platform {
+            return;
+        }
+        let Some(text) = event.keystroke.key_char.as_ref() else {
+            return;
+        };
+        if text.chars().any(char::is_control) {
+            return;
+        }
+        if self
+            .type_ahead_at
+            .is_none_or(|at| at.elapsed() > TYPE_AHEAD_TIMEOUT)
+        {
+            self.type_ahead.clear();
+        }
+        self.type_ahead.push_str(text);
+        self.type_ahead_at = Some(Instant::now());
+
+        let best = self
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(ix, _)| self.is_navigable(*ix))
+            .filter_map(|(ix, item)| {
+                let m = fuzzy::fuzzy_match(&self.type_ahead, item.label()?)?;
+                Some((ix, m.score))
+            })
+            .max_by_key(|(ix, score)| (*score, Reverse(*ix)));
+        if let Some((ix, _)) = best {
+            self.select(Some(ix), window, cx);
+        }
+        cx.stop_propagation();
+    }
+
+    fn select_next(&mut self, _: &SelectNext, window: &mut Window, cx: &mut Context<Self>) {
+        let start = self.selected_index.map_or(0, |ix| ix + 1);
+        let next = (start..self.items.len())
+            .chain(0..start)
+            .find(|ix| self.is_navigable(*ix));
+        self.select(next, window, cx);
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, window: &mut Window, cx: &mut Context<Self>) {
+        let end = self.selected_index.unwrap_or(self.items.len());
+        let previous = (0..end)
+            .rev()
+            .chain((end..self.items.len()).rev())
+            .find(|ix| self.is_navigable(*ix));
+        self.select(previous, window, cx);
+    }
+
+    /// Whether the filter input has focus, which then gets keys like home
+    /// and end for moving its cursor.
+    fn filter_focused(&self, window: &Window, cx: &App) -> bool {
+        self.filter
+            .as_ref()
+            .is_some_and(|filter| filter.focus_handle(cx).is_focused(window))
+    }
+
+    fn select_first(&mut self, _: &SelectFirst, window: &mut Window, cx: &mut Context<Self>) {
+        if self.filter_focused(window, cx) {
+            cx.propagate();
+            return;
+        }
+        let first = (0..self.items.len()).find(|ix| self.is_navigable(*ix));
+        self.select(first, window, cx);
+    }
+
+    fn select_last(&mut self, _: &SelectLast, window: &mut Window, cx: &mut Context<Self>) {
+        if self.filter_focused(window, cx) {
+            cx.propagate();
+            return;
+        }
+        let last = (0..self.items.len()).rfind(|ix| self.is_navigable(*ix));
+        self.select(last, window, cx);
+    }
+
+    fn select_child(&mut self, _: &SelectChild, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(ix) = self
+            .selected_index
+            .filter(|ix| matches!(self.items[*ix], MenuListItem::Submenu { .. }))
+        else {
+            // let an enclosing menu bar move on to the next menu
+            cx.propagate();
+            return;
+        };
+        self.open_submenu(ix, window, cx);
+        if let Some(submenu) = self.submenu.as_ref() {
+            let menu = submenu.menu.clone();
+            window.focus(&menu.focus_handle(cx));
+            menu.update(cx, |menu, cx| menu.select_first(&SelectFirst, window, cx));
+        }
     }
 
-    fn confirm(&mut self, _: &Confirm, _: &mut Window, cx: &mut Context<Self>) {
-        if self.keep_open_on_confirm {
-            todo!();
+    fn select_parent(&mut self, _: &SelectParent, window: &mut Window, cx: &mut Context<Self>) {
+        let submenu_focused = self
+            .submenu
+            .as_ref()
+            .is_some_and(|submenu| submenu.menu.focus_handle(cx).contains_focused(window, cx));
+        if submenu_focused {
+            self.close_submenu(cx);
+            window.focus(&self.focus);
         } else {
+            // let the parent menu, if any, close us
+            cx.propagate();
+        }
+    }
+
+    fn select(&mut self, ix: Option<usize>, window: &mut Window, cx: &mut Context<Self>) {
+        self.pending_hover = None;
+        self.selected_index = ix;
+        if self
+            .submenu
+            .as_ref()
+            .is_some_and(|submenu| Some(submenu.index) != ix)
+        {
+            self.close_submenu(cx);
+        }
+        if let Some(ix) = ix
+            && matches!(self.items[ix], MenuListItem::Submenu { .. })
+        {
+            self.open_submenu(ix, window, cx);
+        }
+        cx.notify();
+    }
+
+    fn activate(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let (handler, action) = match &self.items[ix] {
+            MenuListItem::InteractiveElement { handler, .. } => (handler.clone(), None),
+            MenuListItem::Entry(entry) if !entry.disabled => (
+                entry.handler.clone(),
+                entry.action.as_ref().map(|action| action.boxed_clone()),
+            ),
+            MenuListItem::Submenu { .. } => {
+                self.selected_index = Some(ix);
+                self.select_child(&SelectChild, window, cx);
+                return;
+            }
+            _ => return,
+        };
+
+        // dismiss first so focus is back on the previous element by the time
+        // the handler runs or the action is dispatched
+        if !self.keep_open_on_confirm {
+            self.close_submenu(cx);
             cx.emit(DismissEvent);
         }
+        let action_context = self.action_context.clone();
+        window.defer(cx, move |window, cx| {
+            if let Some(handler) = handler {
+                handler(window, cx);
+            }
+            if let Some(action) = action {
+                match action_context {
+                    Some(focus) => focus.dispatch_action(action.as_ref(), window, cx),
+                    None => window.dispatch_action(action, cx),
+                }
+            }
+        });
     }
+
+    fn hover_item(
+        &mut self,
+        ix: usize,
+        position: Point<Pixels>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let previous_position = self.last_mouse_position.replace(position);
+        let submenu_focused = self
+            .submenu
+            .as_ref()
+            .is_some_and(|submenu| submenu.menu.focus_handle(cx).contains_focused(window, cx));
+        if submenu_focused || !self.focus.contains_focused(window, cx) {
+            window.focus(&self.focus);
+        }
+        if self.selected_index == Some(ix) && self.pending_hover.is_none() {
+            return;
+        }
+
+        let aiming = self
+            .submenu
+            .as_ref()
+            .is_some_and(|submenu| submenu.index != ix)
+            && previous_position.is_some_and(|from| self.is_aiming_at_submenu(from, position, cx));
+        if !aiming {
+            self.select(Some(ix), window, cx);
+            return;
+        }
+
+        self.pending_hover = Some(ix);
+        self._aim_task = Some(cx.spawn_in(window, async move |this, cx| {
+            cx.background_executor().timer(SUBMENU_AIM_TIMEOUT).await;
+            _ = this.update_in(cx, |this, window, cx| {
+                let Some(ix) = this.pending_hover.take() else {
+                    return;
+                };
+                // the mouse made it into the submenu, keep it open
+                if this.submenu_contains(window.mouse_position(), cx) {
+                    return;
+                }
+                this.select(Some(ix), window, cx);
+            });
+        }));
+    }
+
+    /// Checks if the mouse moving from `from` to `to` is heading towards the
+    /// open submenu, i.e. `to` lies in the triangle spanned by `from` and the
+    /// submenu edge facing this menu.
+    fn is_aiming_at_submenu(&self, from: Point<Pixels>, to: Point<Pixels>, cx: &App) -> bool {
+        let Some(submenu) = self.submenu.as_ref() else {
+            return false;
+        };
+        let Some(submenu_bounds) = submenu.menu.read(cx).bounds.get() else {
+            return false;
+        };
+        let edge = if submenu_bounds.left() >= from.x {
+            submenu_bounds.left()
+        } else {
+            submenu_bounds.right()
+        };
+        point_in_triangle(
+            to,
+            from,
+            point(edge, submenu_bounds.top()),
+            point(edge, submenu_bounds.bottom()),
+        )
+    }
+
+    fn submenu_contains(&self, position: Point<Pixels>, cx: &App) -> bool {
+        self.submenu
+            .as_ref()
+            .is_some_and(|submenu| submenu.menu.read(cx).chain_contains(position, cx))
+    }
+
+    fn chain_contains(&self, position: Point<Pixels>, cx: &App) -> bool {
+        self.bounds
+            .get()
+            .is_some_and(|bounds| bounds.contains(&position))
+            || self.submenu_contains(position, cx)
+    }
+
+    fn open_submenu(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        if self
+            .submenu
+            .as_ref()
+            .is_some_and(|submenu| submenu.index == ix)
+        {
+            return;
+        }
+        let MenuListItem::Submenu { builder, .. } = &self.items[ix] else {
+            return;
+        };
+        let builder = builder.clone();
+        let depth = self.depth + 1;
+        let rounding = self.rounding;
+        let interactive_accent_color = self.interactive_accent_color;
+        let action_context = self.action_context.clone();
+        let menu = cx.new(|cx| {
+            let mut menu = Self::new(window, cx, move |menu, window, cx| {
+                builder(menu, window, cx)
+            });
+            menu.depth = depth;
+            menu.rounding = rounding;
+            menu.interactive_accent_color = interactive_accent_color;
+            menu.action_context = action_context;
+            menu
+        });
+        // a dismissed submenu takes the whole chain down with it
+        let subscription = cx.subscribe(&menu, |this, _, _: &DismissEvent, cx| {
+            this.close_submenu(cx);
+            cx.emit(DismissEvent);
+        });
+        self.submenu = Some(OpenSubmenu {
+            index: ix,
+            menu,
+            _subscription: subscription,
+        });
+        cx.notify();
+    }
+
+    fn close_submenu(&mut self, cx: &mut Context<Self>) {
+        if let Some(submenu) = self.submenu.take() {
+            submenu.menu.update(cx, |menu, cx| menu.close_submenu(cx));
+            cx.notify();
+        }
+    }
+
+    fn render_submenu(&self, window: &mut Window, cx: &mut Context<Self>) -> Option<AnyElement> {
+        let submenu = self.submenu.as_ref()?;
+        let row = *self.item_bounds.borrow().get(&submenu.index)?;
+        let width = submenu
+            .menu
+            .read(cx)
+            .bounds
+            .get()
+            .map_or(px(200.0), |bounds| bounds.size.width);
+
+        // flip to the left side when there is no room on the right
+        let (position, corner) = if row.right() + width > window.viewport_size().width {
+            (row.origin, Corner::TopRight)
+        } else {
+            (row.top_right(), Corner::TopLeft)
+        };
+
+        Some(
+            deferred(
+                anchored()
+                    .anchor(corner)
+                    .position(position)
+                    .snap_to_window_with_margin(px(8.0))
+                    .child(div().occlude().child(submenu.menu.clone())),
+            )
+            .with_priority(self.depth + 2)
+            .into_any_element(),
+        )
+    }
+}
+
+fn point_in_triangle(
+    p: Point<Pixels>,
+    a: Point<Pixels>,
+    b: Point<Pixels>,
+    c: Point<Pixels>,
+) -> bool {
+    fn sign(p: Point<Pixels>, a: Point<Pixels>, b: Point<Pixels>) -> f32 {
+        f32::from(p.x - b.x) * f32::from(a.y - b.y) - f32::from(a.x - b.x) * f32::from(p.y - b.y)
+    }
+
+    let d1 = sign(p, a, b);
+    let d2 = sign(p, b, c);
+    let d3 = sign(p, c, a);
+    let has_negative = d1 < 0.0 || d2 < 0.0 || d3 < 0.0;
+    let has_positive = d1 > 0.0 || d2 > 0.0 || d3 > 0.0;
+    !(has_negative && has_positive)
 }
 
 impl Focusable for MenuBuilder {
@@ -128,13 +897,67 @@ impl EventEmitter<DismissEvent> for MenuBuilder {}
 
 impl Render for MenuBuilder {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        v_flex()
+        let is_submenu = self.depth > 0;
+        let submenu = self.render_submenu(window, cx);
+        // Refilled while the rows are laid out, leaving out rows the filter
+        // hides.
+        self.item_bounds.borrow_mut().clear();
+        let bounds = self.bounds.clone();
+
+        let no_matches = !self.query.is_empty() && self.matches.is_empty();
+
+        let style = cx.theme().menu();
+        if self.opacity.is_animating() {
+            window.request_animation_frame();
+        }
+
+        cx.theme()
+            .typography
+            .apply(v_flex())
+            .opacity(self.opacity.value())
             .occlude()
-            .bg(cx.theme().foreground)
-            .rounded_lg()
+            .bg(style.background)
+            .rounded(style.radius)
             .border_1()
-            .border_color(cx.theme().border)
+            .border_color(style.border)
+            .shadow(box_shadows(&cx.theme().shadows.md))
             .flex_shrink_0()
+            .track_focus(&self.focus_handle(cx))
+            // the root menu handles outside clicks for the whole chain
+            .when(!is_submenu, |this| {
+                this.on_mouse_down_out(cx.listener(|this, event: &MouseDownEvent, window, cx| {
+                    if this.submenu_contains(event.position, cx) {
+                        return;
+                    }
+                    this.cancel(&Cancel, window, cx);
+                }))
+            })
+            .key_context("menu")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::select_child))
+            .on_action(cx.listener(Self::select_parent))
+            .on_key_down(cx.listener(Self::type_ahead))
+            .child(
+                canvas(move |b, _, _| bounds.set(Some(b)), |_, _, _, _| {})
+                    .absolute()
+                    .size_full(),
+            )
+            .when_some(self.filter.clone(), |this, filter| {
+                this.child(
+                    div()
+                        .px(cx.theme().spacing.md)
+                        .py_1()
+                        .border_b_1()
+                        .border_color(style.border)
+                        .text_color(style.text)
+                        .child(filter),
+                )
+            })
             .child(
                 v_flex()
                     .gap(px(4.0))
@@ -149,44 +972,109 @@ impl Render for MenuBuilder {
                         this.min_w(px(200.0)).flex_1()
                     })
                     .overflow_y_scroll()
-                    .track_focus(&self.focus_handle(cx))
-                    .on_mouse_down_out(cx.listener(|this, _, window, cx| {
-                        this.cancel(&Cancel, window, cx);
-                    }))
-                    .key_context("menu")
-                    .on_action(cx.listener(Self::cancel))
-                    .on_action(cx.listener(Self::confirm))
+                    .when(no_matches, |this| {
+                        this.child(
+                            div()
+                                .px(cx.theme().spacing.md)
+                                .py_0p5()
+                                .text_color(cx.theme().muted)
+                                .child("No matches"),
+                        )
+                    })
                     .when(!self.delayed, |this| {
-                        let interactive_accent_color = self
-                            .interactive_accent_color
-                            .unwrap_or(cx.theme().selection);
+                        let interactive_accent_color =
+                            self.interactive_accent_color.unwrap_or(style.hover);
                         let rounding = self.rounding;
-                        this.children(self.items.iter().enumerate().map(|(i, item)| match item {
-                            MenuListItem::NonInteractive(render) => {
-                                render(window, cx).into_any_element()
-                            }
-                            MenuListItem::InteractiveElement { render, handler } => {
-                                let menu = cx.entity().downgrade();
-                                let handler = handler.clone();
+                        let selected_index = self.selected_index;
+                        let text_color = style.text;
+                        let filtering = !self.query.is_empty();
+                        let items = self
+                            .items
+                            .iter()
+                            .enumerate()
+                            .filter(|(i, _)| !filtering || self.matches.contains_key(i));
+                        this.children(items.map(|(i, item)| {
+                            let row = |content: AnyElement, cx: &mut Context<Self>| {
+                                let item_bounds = self.item_bounds.clone();
                                 Button::new(("ctx-btn", i))
                                     .rounded(rounding)
-                                    .child(render(window, cx))
-                                    .hover(interactive_accent_color)
-                                    .when_some(handler, |this, handler| {
-                                        this.on_mouse_down(
-                                            MouseButton::Left,
-                                            move |_, window, cx| {
-                                                handler(window, cx);
-                                                _ = menu.update(cx, |_, cx| {
-                                                    cx.emit(DismissEvent);
-                                                });
+                                    .child(
+                                        canvas(
+                                            move |b, _, _| {
+                                                item_bounds.borrow_mut().insert(i, b);
                                             },
+                                            |_, _, _, _| {},
                                         )
+                                        .absolute()
+                                        .size_full(),
+                                    )
+                                    .child(content)
+                                    .hover(interactive_accent_color)
+                                    .when(selected_index == Some(i), |this| {
+                                        this.base(interactive_accent_color)
                                     })
-                                    .into_any_element()
+                                    .on_mouse_move(cx.listener(
+                                        move |this, event: &MouseMoveEvent, window, cx| {
+                                            this.hover_item(i, event.position, window, cx);
+                                        },
+                                    ))
+                                    .on_mouse_down(
+                                        MouseButton::Left,
+                                        cx.listener(move |this, _, window, cx| {
+                                            cx.stop_propagation();
+                                            this.activate(i, window, cx);
+                                        }),
+                                    )
+                            };
+                            match item {
+                                MenuListItem::NonInteractive(render) => {
+                                    render(window, cx).into_any_element()
+                                }
+                                MenuListItem::InteractiveElement { render, .. } => {
+                                    row(render(window, cx), cx).into_any_element()
+                                }
+                                MenuListItem::Submenu { render, .. } => row(
+                                    h_flex()
+                                        .justify_between()
+                                        .items_center()
+                                        .child(render(window, cx))
+                                        .child(
+                                            svg()
+                                                .text_color(text_color)
+                                                .path("chevron_right")
+                                                .flex_none()
+                                                .size_4(),
+                                        )
+                                        .into_any_element(),
+                                    cx,
+                                )
+                                .into_any_element(),
+                                MenuListItem::Entry(entry) => {
+                                    let highlights = self.matches.get(&i).map(Vec::as_slice);
+                                    let content = entry.render(
+                                        self.action_context.as_ref(),
+                                        highlights,
+                                        window,
+                                        cx,
+                                    );
+                                    if entry.disabled {
+                                        content
+                                    } else {
+                                        row(content, cx).into_any_element()
+                                    }
+                                }
+                                MenuListItem::Separator => Divider::horizontal().into_any_element(),
+                                MenuListItem::Header(label) => div()
+                                    .px(cx.theme().spacing.md)
+                                    .pt_1()
+                                    .text_size(cx.theme().typography.text_xs)
+                                    .text_color(cx.theme().muted)
+                                    .child(label.clone())
+                                    .into_any_element(),
                             }
                         }))
-                    }),
+                    })
+                    .children(submenu),
             )
     }
 }
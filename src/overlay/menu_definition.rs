@@ -0,0 +1,374 @@
+use std::{fs, io, path::Path, rc::Rc};
+
+use crate::prelude::*;
+use gpui::{prelude::*, *};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("failed to parse menu: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("io: {0}")]
+    Io(#[from] io::Error),
+    #[error("unknown action `{action}` in menu item `{item}`")]
+    UnknownAction { item: String, action: String },
+    #[error("failed to build action `{action}` in menu item `{item}`: {message}")]
+    InvalidAction {
+        item: String,
+        action: String,
+        message: String,
+    },
+    #[error("invalid `enabled_when` predicate in menu item `{item}`: {message}")]
+    InvalidPredicate { item: String, message: String },
+}
+
+type Result<T> = std::result::Result<T, Error>;
+
+/// Serializable description of a menu, e.g. contributed by a plugin or
+/// customized by the user, stored as a JSON array of items.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct MenuDefinition {
+    pub items: Vec<MenuItemDefinition>,
+}
+
+/// A single menu item. Items with `separator` or `header` set become
+/// separators or section headers, items with `children` become submenus and
+/// all others dispatch `action` when confirmed.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MenuItemDefinition {
+    pub label: String,
+    /// Name of a registered action, e.g. `text_input::Copy`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<String>,
+    /// Arguments passed to the action when it is built.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub args: Option<serde_json::Value>,
+    /// Svg asset path of the icon shown in front of the label.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    /// Overrides the key binding looked up for `action`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shortcut: Option<String>,
+    /// Key context predicate, e.g. `TextInput && !menu`. The item is disabled
+    /// unless it matches the context the menu is opened in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled_when: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<MenuItemDefinition>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub separator: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub header: bool,
+}
+
+impl MenuDefinition {
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_json(&fs::read_to_string(path)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let str = serde_json::to_string_pretty(self)?;
+        fs::write(path, &str)?;
+        Ok(())
+    }
+
+    /// Resolves all action names and predicates against the actions
+    /// registered in `cx`, failing on the first one that is unknown.
+    pub fn resolve(&self, cx: &App) -> Result<ResolvedMenu> {
+        Ok(ResolvedMenu(Rc::new(resolve_items(&self.items, "", cx)?)))
+    }
+
+    /// Shorthand for resolving the definition and building a menu from it.
+    pub fn build(&self, window: &mut Window, cx: &mut App) -> Result<Entity<MenuBuilder>> {
+        Ok(self.resolve(cx)?.build(window, cx))
+    }
+}
+
+enum ResolvedItem {
+    Separator,
+    Header(SharedString),
+    Entry {
+        label: SharedString,
+        icon: Option<SharedString>,
+        shortcut: Option<SharedString>,
+        action: Option<Box<dyn Action>>,
+        enabled_when: Option<KeyBindingContextPredicate>,
+    },
+    Submenu {
+        label: SharedString,
+        icon: Option<SharedString>,
+        enabled_when: Option<KeyBindingContextPredicate>,
+        children: Rc<Vec<ResolvedItem>>,
+    },
+}
+
+fn resolve_items(
+    items: &[MenuItemDefinition],
+    parent: &str,
+    cx: &App,
+) -> Result<Vec<ResolvedItem>> {
+    items
+        .iter()
+        .map(|item| {
+            let path = if parent.is_empty() {
+                item.label.clone()
+            } else {
+                format!("{parent} > {}", item.label)
+            };
+            if item.separator {
+                return Ok(ResolvedItem::Separator);
+            }
+            if item.header {
+                return Ok(ResolvedItem::Header(item.label.clone().into()));
+            }
+
+            let enabled_when = item
+                .enabled_when
+                .as_deref()
+                .map(|source| {
+                    KeyBindingContextPredicate::parse(source).map_err(|error| {
+                        Error::InvalidPredicate {
+                            item: path.clone(),
+                            message: error.to_string(),
+                        }
+                    })
+                })
+                .transpose()?;
+            let icon = item.icon.clone().map(SharedString::from);
+
+            if !item.children.is_empty() {
+                return Ok(ResolvedItem::Submenu {
+                    label: item.label.clone().into(),
+                    icon,
+                    enabled_when,
+                    children: Rc::new(resolve_items(&item.children, &path, cx)?),
+                });
+            }
+
+            let action = item
+                .action
+                .as_deref()
+                .map(|name| {
+                    if !cx.all_action_names().iter().any(|known| *known == name) {
+                        return Err(Error::UnknownAction {
+                            item: path.clone(),
+                            action: name.to_string(),
+                        });
+                    }
+                    cx.build_action(name, item.args.clone())
+                        .map_err(|error| Error::InvalidAction {
+                            item: path.clone(),
+                            action: name.to_string(),
+                            message: error.to_string(),
+                        })
+                })
+                .transpose()?;
+
+            Ok(ResolvedItem::Entry {
+                label: item.label.clone().into(),
+                icon,
+                shortcut: item.shortcut.clone().map(SharedString::from),
+                action,
+                enabled_when,
+            })
+        })
+        .collect()
+}
+
+/// A [`MenuDefinition`] whose actions have been resolved. Cheap to clone, so
+/// it can be moved into a `CtxMenu::menu` builder.
+#[derive(Clone)]
+pub struct ResolvedMenu(Rc<Vec<ResolvedItem>>);
+
+impl ResolvedMenu {
+    pub fn build(&self, window: &mut Window, cx: &mut App) -> Entity<MenuBuilder> {
+        let items = self.0.clone();
+        // Taken before the menu gets focus, which would add its own context.
+        let contexts = Rc::new(window.context_stack());
+        MenuBuilder::build(window, cx, move |menu, _, _| {
+            apply_items(&items, menu, &contexts)
+        })
+    }
+
+    /// Appends the items to `menu`, evaluating `enabled_when` against the
+    /// currently focused key context.
+    pub fn apply(&self, menu: MenuBuilder, window: &Window) -> MenuBuilder {
+        apply_items(&self.0, menu, &Rc::new(window.context_stack()))
+    }
+}
+
+/// Submenus are filled when they open, by which time the menu has focus, so
+/// they get the `contexts` of the menu they belong to passed down.
+fn apply_items(
+    items: &[ResolvedItem],
+    mut menu: MenuBuilder,
+    contexts: &Rc<Vec<KeyContext>>,
+) -> MenuBuilder {
+    let enabled = |predicate: &Option<KeyBindingContextPredicate>| {
+        predicate
+            .as_ref()
+            .is_none_or(|predicate| predicate.depth_of(contexts).is_some())
+    };
+
+    for item in items.iter() {
+        menu = match item {
+            ResolvedItem::Separator => menu.separator(),
+            ResolvedItem::Header(label) => menu.header(label.clone()),
+            ResolvedItem::Entry {
+                label,
+                icon,
+                shortcut,
+                action,
+                enabled_when,
+            } => {
+                let mut entry = MenuEntry::new(label.clone())
+                    .disabled(action.is_none() || !enabled(enabled_when));
+                if let Some(icon) = icon.clone() {
+                    entry = entry.icon(icon);
+                }
+                if let Some(shortcut) = shortcut.clone() {
+                    entry = entry.shortcut(shortcut);
+                }
+                if let Some(action) = action {
+                    entry = entry.action(action.boxed_clone());
+                }
+                menu.item(entry)
+            }
+            ResolvedItem::Submenu {
+                label,
+                icon,
+                enabled_when,
+                ..
+            } if !enabled(enabled_when) => {
+                let mut entry = MenuEntry::new(label.clone()).disabled(true);
+                if let Some(icon) = icon.clone() {
+                    entry = entry.icon(icon);
+                }
+                menu.item(entry)
+            }
+            ResolvedItem::Submenu {
+                label,
+                icon,
+                children,
+                ..
+            } => {
+                let children = children.clone();
+                let contexts = contexts.clone();
+                let (row_label, icon) = (label.clone(), icon.clone());
+                menu.submenu(
+                    move |_, cx| {
+                        h_flex()
+                            .gap_2()
+                            .px(cx.theme().spacing.md)
+                            .py_0p5()
+                            .items_center()
+                            .text_color(cx.theme().text)
+                            .when_some(icon.clone(), |this, icon| {
+                                this.child(
+                                    svg()
+                                        .text_color(cx.theme().text)
+                                        .flex_none()
+                                        .size_4()
+                                        .path(icon),
+                                )
+                            })
+                            .child(row_label.clone())
+                            .into_any_element()
+                    },
+                    move |menu, _, _| apply_items(&children, menu, &contexts),
+                )
+                .search_label(label.clone())
+            }
+        };
+    }
+    menu
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn item(label: &str, action: &str) -> MenuItemDefinition {
+        MenuItemDefinition {
+            label: label.to_string(),
+            action: Some(action.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[gpui::test]
+    fn resolves_items_from_json(cx: &mut TestAppContext) {
+        let definition = MenuDefinition::from_json(
+            r#"[
+                { "label": "Edit", "header": true },
+                { "label": "Confirm", "action": "menu::Confirm", "enabled_when": "menu" },
+                { "separator": true },
+                { "label": "More", "children": [{ "label": "Cancel", "action": "menu::Cancel" }] }
+            ]"#,
+        )
+        .unwrap();
+        let resolved = cx.update(|cx| definition.resolve(cx)).unwrap();
+
+        let [
+            ResolvedItem::Header(header),
+            ResolvedItem::Entry {
+                action: Some(action),
+                enabled_when: Some(_),
+                ..
+            },
+            ResolvedItem::Separator,
+            ResolvedItem::Submenu { children, .. },
+        ] = resolved.0.as_slice()
+        else {
+            panic!("unexpected items");
+        };
+        assert_eq!(header, "Edit");
+        assert!(action.partial_eq(&crate::overlay::menu_builder::Confirm));
+        assert!(matches!(
+            children.as_slice(),
+            [ResolvedItem::Entry {
+                action: Some(_),
+                ..
+            }]
+        ));
+    }
+
+    #[gpui::test]
+    fn reports_unresolvable_items(cx: &mut TestAppContext) {
+        let resolve = |items: Vec<MenuItemDefinition>, cx: &mut TestAppContext| {
+            cx.update(|cx| MenuDefinition { items }.resolve(cx).err())
+        };
+
+        let nested = MenuItemDefinition {
+            label: "File".to_string(),
+            children: vec![item("Open", "menu::DoesNotExist")],
+            ..Default::default()
+        };
+        assert!(matches!(
+            resolve(vec![nested], cx),
+            Some(Error::UnknownAction { item, action })
+                if item == "File > Open" && action == "menu::DoesNotExist"
+        ));
+
+        // Only buildable from code, not from JSON.
+        assert!(matches!(
+            resolve(vec![item("First tab", "tabs::ActivateTab")], cx),
+            Some(Error::InvalidAction { item, .. }) if item == "First tab"
+        ));
+
+        let predicate = MenuItemDefinition {
+            enabled_when: Some("TextInput &&".to_string()),
+            ..item("Copy", "menu::Confirm")
+        };
+        assert!(matches!(
+            resolve(vec![predicate], cx),
+            Some(Error::InvalidPredicate { item, .. }) if item == "Copy"
+        ));
+    }
+}
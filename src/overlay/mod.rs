@@ -1,4 +1,9 @@
+pub mod command_palette;
 pub mod menu;
 pub mod menu_builder;
+pub mod menu_definition;
+pub mod modal;
+pub mod notification;
+pub mod popover;
 pub mod popup;
 pub mod tooltip;
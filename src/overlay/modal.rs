@@ -0,0 +1,439 @@
+use std::{collections::HashMap, future::Future};
+
+use crate::input::EnrichedInputAction;
+use crate::overlay::menu_builder::{Cancel, Confirm};
+use crate::overlay::popover::{FocusNext, FocusPrevious};
+use crate::prelude::*;
+use futures::channel::oneshot;
+use gpui::{prelude::*, *};
+
+pub fn init(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("tab", FocusNext, Some("Modal")),
+        KeyBinding::new("shift-tab", FocusPrevious, Some("Modal")),
+        KeyBinding::new("escape", Cancel, Some("Dialog")),
+        KeyBinding::new("enter", Confirm, Some("Dialog")),
+    ]);
+}
+
+/// Modal layers of all open windows, used by the dialog helpers to find the
+/// layer of the window they are called for.
+#[derive(Default)]
+struct ModalLayers(HashMap<WindowId, WeakEntity<ModalLayer>>);
+
+impl Global for ModalLayers {}
+
+struct ActiveModal {
+    view: AnyView,
+    focus: FocusHandle,
+    previous_focus: Option<FocusHandle>,
+    _subscription: Subscription,
+}
+
+/// Stack of modal views drawn above the whole window. Every modal dims
+/// everything below it and keeps the focus until it emits `DismissEvent`.
+///
+/// The root view of a window creates the layer once and renders it as one of
+/// its children; the layer positions itself.
+pub struct ModalLayer {
+    stack: Vec<ActiveModal>,
+}
+
+impl ModalLayer {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let layers = &mut cx.default_global::<ModalLayers>().0;
+        // Layers of closed windows are gone by now.
+        layers.retain(|_, layer| layer.upgrade().is_some());
+        layers.insert(window.window_handle().window_id(), cx.weak_entity());
+        Self { stack: Vec::new() }
+    }
+
+    /// Returns the layer rendered in `window`, if its root created one.
+    pub fn for_window(window: &Window, cx: &App) -> Option<Entity<Self>> {
+        cx.try_global::<ModalLayers>()?
+            .0
+            .get(&window.window_handle().window_id())?
+            .upgrade()
+    }
+
+    pub fn has_active_modal(&self) -> bool {
+        !self.stack.is_empty()
+    }
+
+    /// Shows `view` on top of all other modals and focuses it.
+    pub fn push<V: ManagedView>(
+        &mut self,
+        view: Entity<V>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let focus = view.focus_handle(cx);
+        let id = view.entity_id();
+        let subscription = cx.subscribe_in(
+            &view,
+            window,
+            move |this, _, _: &DismissEvent, window, cx| {
+                this.remove(id, window, cx);
+            },
+        );
+        self.stack.push(ActiveModal {
+            view: view.into(),
+            focus: focus.clone(),
+            previous_focus: window.focused(cx),
+            _subscription: subscription,
+        });
+        window.focus(&focus);
+        cx.notify();
+    }
+
+    fn remove(&mut self, id: EntityId, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(ix) = self
+            .stack
+            .iter()
+            .position(|modal| modal.view.entity_id() == id)
+        else {
+            return;
+        };
+        let modal = self.stack.remove(ix);
+        if ix == self.stack.len() {
+            match (modal.previous_focus, self.stack.last()) {
+                (Some(previous_focus), _) => window.focus(&previous_focus),
+                (None, Some(top)) => window.focus(&top.focus),
+                (None, None) => {}
+            }
+        }
+        cx.notify();
+    }
+
+    /// Moves the focus back into the topmost modal if it escaped.
+    fn trap_focus(&self, window: &mut Window, cx: &mut App) {
+        if let Some(top) = self.stack.last()
+            && !top.focus.contains_focused(window, cx)
+        {
+            window.focus(&top.focus);
+        }
+    }
+}
+
+impl Render for ModalLayer {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if self.stack.is_empty() {
+            return div().into_any_element();
+        }
+
+        let top = self.stack.len() - 1;
+        deferred(anchored().position(point(px(0.0), px(0.0))).child(
+            div().relative().size(window.viewport_size()).children(
+                self.stack.iter().enumerate().map(|(ix, modal)| {
+                    div()
+                        .id(("modal", ix))
+                        .absolute()
+                        .inset_0()
+                        .occlude()
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .bg(cx.theme().backdrop)
+                        .when(ix == top, |this| {
+                            this.key_context("Modal")
+                                .on_action(cx.listener(|this, _: &FocusNext, window, cx| {
+                                    window.focus_next();
+                                    this.trap_focus(window, cx);
+                                }))
+                                .on_action(cx.listener(|this, _: &FocusPrevious, window, cx| {
+                                    window.focus_prev();
+                                    this.trap_focus(window, cx);
+                                }))
+                                // Clicks on the backdrop must not take the focus away.
+                                .on_mouse_down(
+                                    MouseButton::Left,
+                                    cx.listener(|this, _, window, cx| {
+                                        window.prevent_default();
+                                        this.trap_focus(window, cx);
+                                    }),
+                                )
+                        })
+                        .child(modal.view.clone())
+                }),
+            ),
+        ))
+        .with_priority(3)
+        .into_any_element()
+    }
+}
+
+/// What the user answered a [`Dialog`] with. Both fields are `None` if the
+/// dialog was cancelled.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DialogResponse {
+    /// Index of the clicked button.
+    pub button: Option<usize>,
+    /// Content of the text field, if the dialog has one.
+    pub text: Option<String>,
+}
+
+/// A modal dialog with a title, an optional message and text field and a
+/// row of buttons. Use [`Dialog::open`] or the [`alert`], [`confirm`] and
+/// [`prompt`] helpers to show one.
+pub struct Dialog {
+    focus: FocusHandle,
+    title: SharedString,
+    message: Option<SharedString>,
+    input: Option<Entity<TextInput>>,
+    text: String,
+    buttons: Vec<SharedString>,
+    /// Button chosen when pressing enter.
+    primary: usize,
+    response: Option<oneshot::Sender<DialogResponse>>,
+    _subscription: Option<Subscription>,
+}
+
+impl Dialog {
+    pub fn new(title: impl Into<SharedString>, cx: &mut Context<Self>) -> Self {
+        Self {
+            focus: cx.focus_handle(),
+            title: title.into(),
+            message: None,
+            input: None,
+            text: String::new(),
+            buttons: Vec::new(),
+            primary: 0,
+            response: None,
+            _subscription: None,
+        }
+    }
+
+    pub fn message(self, message: impl Into<SharedString>) -> Self {
+        Self {
+            message: Some(message.into()),
+            ..self
+        }
+    }
+
+    /// Adds a text field initialized with `text`.
+    pub fn input(
+        mut self,
+        text: impl Into<SharedString>,
+        placeholder: Option<SharedString>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let text = text.into();
+        let dialog = cx.weak_entity();
+        let input = cx.new(|cx| {
+            TextInput::new(
+                cx.focus_handle(),
+                Some(text.clone()),
+                placeholder,
+                // The input takes enter for itself, so it confirms from here.
+                Some(Box::new(move |action, window, cx| {
+                    if let EnrichedInputAction::Accept = action {
+                        _ = dialog.update(cx, |this, cx| this.confirm(&Confirm, window, cx));
+                    }
+                })),
+            )
+        });
+        self._subscription = Some(cx.subscribe(&input, |this, _, text: &String, _| {
+            this.text = text.clone();
+        }));
+        self.text = text.to_string();
+        self.input = Some(input);
+        self
+    }
+
+    pub fn button(mut self, label: impl Into<SharedString>) -> Self {
+        self.buttons.push(label.into());
+        self
+    }
+
+    /// Marks the button at `ix` as the one confirmed with enter. Call it
+    /// after adding the buttons.
+    pub fn primary(self, ix: usize) -> Self {
+        debug_assert!(
+            ix < self.buttons.len(),
+            "primary button {ix} out of range, the dialog has {} buttons",
+            self.buttons.len()
+        );
+        Self {
+            primary: ix,
+            ..self
+        }
+    }
+
+    /// Shows the dialog built by `build` in the modal layer of `window`. The
+    /// returned future resolves once the dialog is closed, or immediately with
+    /// an empty response if the window has no [`ModalLayer`].
+    pub fn open(
+        window: &mut Window,
+        cx: &mut App,
+        build: impl FnOnce(&mut Window, &mut Context<Self>) -> Self,
+    ) -> impl Future<Output = DialogResponse> + 'static {
+        let (tx, rx) = oneshot::channel();
+        if let Some(layer) = ModalLayer::for_window(window, cx) {
+            let dialog = cx.new(|cx| {
+                let mut dialog = build(window, cx);
+                dialog.response = Some(tx);
+                dialog
+            });
+            layer.update(cx, |layer, cx| layer.push(dialog, window, cx));
+        }
+        async move { rx.await.unwrap_or_default() }
+    }
+
+    fn respond(&mut self, button: Option<usize>, cx: &mut Context<Self>) {
+        if let Some(response) = self.response.take() {
+            _ = response.send(DialogResponse {
+                button,
+                text: button
+                    .is_some()
+                    .then(|| self.input.as_ref().map(|_| self.text.clone()))
+                    .flatten(),
+            });
+        }
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &Confirm, _: &mut Window, cx: &mut Context<Self>) {
+        if self.primary < self.buttons.len() {
+            self.respond(Some(self.primary), cx);
+        }
+    }
+
+    fn cancel(&mut self, _: &Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        self.respond(None, cx);
+    }
+}
+
+impl Focusable for Dialog {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        match self.input.as_ref() {
+            Some(input) => input.focus_handle(cx),
+            None => self.focus.clone(),
+        }
+    }
+}
+
+impl EventEmitter<DismissEvent> for Dialog {}
+
+impl Render for Dialog {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let selection = cx.theme().selection;
+
+        v_flex()
+            .id("dialog")
+            .track_focus(&self.focus)
+            .key_context("Dialog")
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .w(px(400.0))
+            .p_4()
+            .gap_3()
+            .bg(cx.theme().foreground)
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded(cx.theme().radii.lg)
+            .shadow(box_shadows(&cx.theme().shadows.lg))
+            .text_color(cx.theme().text)
+            .child(
+                div()
+                    .text_lg()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .child(self.title.clone()),
+            )
+            .when_some(self.message.clone(), |this, message| {
+                this.child(
+                    Label::new(message)
+                        .no_border()
+                        .x_align(TextAlignment::Start),
+                )
+            })
+            .when_some(self.input.clone(), |this, input| {
+                this.child(
+                    div()
+                        .px(cx.theme().spacing.md)
+                        .py_1()
+                        .border_1()
+                        .border_color(cx.theme().border)
+                        .rounded(cx.theme().radii.lg)
+                        .child(input),
+                )
+            })
+            .child(
+                h_flex()
+                    .justify_end()
+                    .gap_2()
+                    .children(self.buttons.iter().enumerate().map(|(ix, label)| {
+                        Button::new(("dialog-button", ix))
+                            .bordered()
+                            .px_3()
+                            .py_1()
+                            .hover(selection)
+                            .when(ix == self.primary, |this| this.base(selection))
+                            .child(Label::new(label.clone()).no_border())
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.respond(Some(ix), cx);
+                            }))
+                    })),
+            )
+    }
+}
+
+/// Shows a message with a single "OK" button.
+pub fn alert(
+    title: impl Into<SharedString>,
+    message: impl Into<SharedString>,
+    window: &mut Window,
+    cx: &mut App,
+) -> impl Future<Output = ()> + 'static {
+    let (title, message) = (title.into(), message.into());
+    let response = Dialog::open(window, cx, |_, cx| {
+        Dialog::new(title, cx).message(message).button("OK")
+    });
+    async move {
+        response.await;
+    }
+}
+
+/// Asks a yes/no question. Resolves to `true` if the user confirmed.
+pub fn confirm(
+    title: impl Into<SharedString>,
+    message: impl Into<SharedString>,
+    window: &mut Window,
+    cx: &mut App,
+) -> impl Future<Output = bool> + 'static {
+    let (title, message) = (title.into(), message.into());
+    let response = Dialog::open(window, cx, |_, cx| {
+        Dialog::new(title, cx)
+            .message(message)
+            .button("Cancel")
+            .button("OK")
+            .primary(1)
+    });
+    async move { response.await.button == Some(1) }
+}
+
+/// Asks for a line of text. Resolves to `None` if the user cancelled.
+pub fn prompt(
+    title: impl Into<SharedString>,
+    message: impl Into<SharedString>,
+    default: impl Into<SharedString>,
+    window: &mut Window,
+    cx: &mut App,
+) -> impl Future<Output = Option<String>> + 'static {
+    let (title, message, default) = (title.into(), message.into(), default.into());
+    let response = Dialog::open(window, cx, |_, cx| {
+        Dialog::new(title, cx)
+            .message(message)
+            .input(default, None, cx)
+            .button("Cancel")
+            .button("OK")
+            .primary(1)
+    });
+    async move {
+        let response = response.await;
+        if response.button == Some(1) {
+            response.text
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,481 @@
+use std::{
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use crate::prelude::*;
+use gpui::{prelude::*, *};
+
+/// Time a toast stays visible unless [`Toast::duration`] says otherwise.
+const DEFAULT_DURATION: Duration = Duration::from_secs(5);
+/// Number of dismissed notifications kept for the history panel.
+const MAX_HISTORY: usize = 50;
+/// Maximum number of toasts shown at once, older ones are queued.
+const MAX_VISIBLE: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Severity {
+    #[default]
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub fn color(self, theme: &Theme) -> Hsla {
+        match self {
+            Self::Info => theme.hint,
+            Self::Warning => theme.warning,
+            Self::Error => theme.error,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct ToastAction {
+    label: SharedString,
+    handler: Rc<dyn Fn(&mut Window, &mut App)>,
+}
+
+/// A notification shown in the corner of every window until it times out or
+/// the user closes it.
+#[derive(Clone)]
+pub struct Toast {
+    severity: Severity,
+    title: SharedString,
+    message: Option<SharedString>,
+    actions: Vec<ToastAction>,
+    duration: Option<Duration>,
+}
+
+impl Toast {
+    pub fn new(severity: Severity, title: impl Into<SharedString>) -> Self {
+        Self {
+            severity,
+            title: title.into(),
+            message: None,
+            actions: Vec::new(),
+            duration: Some(DEFAULT_DURATION),
+        }
+    }
+
+    pub fn info(title: impl Into<SharedString>) -> Self {
+        Self::new(Severity::Info, title)
+    }
+
+    pub fn warning(title: impl Into<SharedString>) -> Self {
+        Self::new(Severity::Warning, title)
+    }
+
+    pub fn error(title: impl Into<SharedString>) -> Self {
+        Self::new(Severity::Error, title)
+    }
+
+    pub fn message(self, message: impl Into<SharedString>) -> Self {
+        Self {
+            message: Some(message.into()),
+            ..self
+        }
+    }
+
+    /// Adds a button which runs `handler` and dismisses the toast.
+    pub fn action(
+        mut self,
+        label: impl Into<SharedString>,
+        handler: impl Fn(&mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.actions.push(ToastAction {
+            label: label.into(),
+            handler: Rc::new(handler),
+        });
+        self
+    }
+
+    /// Time until the toast dismisses itself, `None` keeps it until closed.
+    pub fn duration(self, duration: Option<Duration>) -> Self {
+        Self { duration, ..self }
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    pub fn title(&self) -> &SharedString {
+        &self.title
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ToastId(usize);
+
+struct ActiveToast {
+    id: ToastId,
+    toast: Toast,
+    /// Time left on the dismiss timer when it was last paused.
+    remaining: Duration,
+    /// When the timer last started running, `None` while paused.
+    resumed_at: Option<Instant>,
+    hovered: bool,
+    /// Dismisses the toast once its time is up, dropped while paused.
+    timer: Option<Task<()>>,
+}
+
+impl ActiveToast {
+    fn time_left(&self) -> Duration {
+        match self.resumed_at {
+            Some(resumed_at) => self.remaining.saturating_sub(resumed_at.elapsed()),
+            None => self.remaining,
+        }
+    }
+}
+
+/// Application wide notification queue. Render a [`NotificationStack`] in
+/// every window that should show toasts and a [`NotificationHistory`] where
+/// dismissed ones can be reviewed.
+pub struct Notifications {
+    next_id: usize,
+    active: Vec<ActiveToast>,
+    history: Vec<Toast>,
+    corner: Corner,
+}
+
+impl Global for Notifications {}
+
+impl Default for Notifications {
+    fn default() -> Self {
+        Self {
+            next_id: 0,
+            active: Vec::new(),
+            history: Vec::new(),
+            corner: Corner::BottomRight,
+        }
+    }
+}
+
+impl Notifications {
+    /// Queues `toast` and returns an id to dismiss it from code.
+    pub fn push(toast: Toast, cx: &mut App) -> ToastId {
+        let notifications = cx.default_global::<Self>();
+        let id = ToastId(notifications.next_id);
+        notifications.next_id += 1;
+        notifications.active.push(ActiveToast {
+            id,
+            remaining: toast.duration.unwrap_or_default(),
+            resumed_at: None,
+            hovered: false,
+            timer: None,
+            toast,
+        });
+        Self::start_visible_timers(cx);
+        cx.refresh_windows();
+        id
+    }
+
+    pub fn dismiss(id: ToastId, cx: &mut App) {
+        let notifications = cx.default_global::<Self>();
+        let Some(ix) = notifications
+            .active
+            .iter()
+            .position(|active| active.id == id)
+        else {
+            return;
+        };
+        let active = notifications.active.remove(ix);
+        notifications.history.insert(0, active.toast);
+        notifications.history.truncate(MAX_HISTORY);
+        Self::start_visible_timers(cx);
+        cx.refresh_windows();
+    }
+
+    pub fn set_corner(corner: Corner, cx: &mut App) {
+        cx.default_global::<Self>().corner = corner;
+        cx.refresh_windows();
+    }
+
+    pub fn clear_history(cx: &mut App) {
+        cx.default_global::<Self>().history.clear();
+        cx.refresh_windows();
+    }
+
+    /// Dismissed notifications, most recent first.
+    pub fn history(cx: &App) -> &[Toast] {
+        cx.try_global::<Self>()
+            .map(|notifications| notifications.history.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Starts the timers of toasts that just became visible. Queued toasts
+    /// don't count down until there is room for them.
+    fn start_visible_timers(cx: &mut App) {
+        let notifications = cx.global_mut::<Self>();
+        let mut started = Vec::new();
+        for active in notifications.active.iter_mut().take(MAX_VISIBLE) {
+            if active.toast.duration.is_some() && active.resumed_at.is_none() && !active.hovered {
+                active.resumed_at = Some(Instant::now());
+                started.push(active.id);
+            }
+        }
+        for id in started {
+            Self::start_timer(id, cx);
+        }
+    }
+
+    /// Replaces the timer of the toast with one for its remaining time.
+    fn start_timer(id: ToastId, cx: &mut App) {
+        let Some(remaining) = cx
+            .global::<Self>()
+            .active
+            .iter()
+            .find(|active| active.id == id)
+            .map(|active| active.remaining)
+        else {
+            return;
+        };
+        let timer = Self::watch_timer(id, remaining, cx);
+        if let Some(active) = cx
+            .global_mut::<Self>()
+            .active
+            .iter_mut()
+            .find(|active| active.id == id)
+        {
+            active.timer = Some(timer);
+        }
+    }
+
+    fn watch_timer(id: ToastId, delay: Duration, cx: &mut App) -> Task<()> {
+        cx.spawn(async move |cx| {
+            let mut delay = delay;
+            loop {
+                cx.background_executor().timer(delay).await;
+                let next = cx.update(|cx| {
+                    let active = cx
+                        .try_global::<Self>()?
+                        .active
+                        .iter()
+                        .find(|active| active.id == id)?;
+                    // The timer gets restarted when the pointer leaves.
+                    active.resumed_at?;
+                    let left = active.time_left();
+                    if left.is_zero() {
+                        Self::dismiss(id, cx);
+                        None
+                    } else {
+                        Some(left)
+                    }
+                });
+                match next {
+                    Ok(Some(left)) => delay = left,
+                    _ => break,
+                }
+            }
+        })
+    }
+
+    fn set_hovered(id: ToastId, hovered: bool, cx: &mut App) {
+        let Some(active) = cx
+            .default_global::<Self>()
+            .active
+            .iter_mut()
+            .find(|active| active.id == id)
+        else {
+            return;
+        };
+        active.hovered = hovered;
+        if active.toast.duration.is_none() {
+            return;
+        }
+        if hovered {
+            active.remaining = active.time_left();
+            active.resumed_at = None;
+            active.timer = None;
+        } else if active.resumed_at.is_none() {
+            active.resumed_at = Some(Instant::now());
+            Self::start_timer(id, cx);
+        }
+    }
+}
+
+fn render_toast(
+    id: ElementId,
+    toast: &Toast,
+    on_close: Option<Rc<dyn Fn(&mut Window, &mut App)>>,
+    cx: &App,
+) -> Stateful<Div> {
+    let theme = cx.theme();
+    h_flex()
+        .id(id)
+        .items_start()
+        .gap_2()
+        .p_2()
+        .bg(theme.foreground)
+        .border_1()
+        .border_color(theme.border)
+        .rounded(theme.radii.lg)
+        .shadow(box_shadows(&theme.shadows.md))
+        .text_color(theme.text)
+        .child(
+            div()
+                .w(px(3.0))
+                .h_full()
+                .flex_none()
+                .rounded(theme.radii.sm)
+                .bg(toast.severity.color(theme)),
+        )
+        .child(
+            v_flex()
+                .flex_1()
+                .gap_1()
+                .child(
+                    div()
+                        .font_weight(FontWeight::SEMIBOLD)
+                        .child(toast.title.clone()),
+                )
+                .when_some(toast.message.clone(), |this, message| {
+                    this.child(div().text_color(theme.muted).child(message))
+                })
+                .when(!toast.actions.is_empty() && on_close.is_some(), |this| {
+                    this.child(
+                        h_flex()
+                            .gap_2()
+                            .children(toast.actions.iter().enumerate().map(|(ix, action)| {
+                                let handler = action.handler.clone();
+                                let on_close = on_close.clone();
+                                Button::new(("toast-action", ix))
+                                    .bordered()
+                                    .px(theme.spacing.md)
+                                    .hover(theme.selection)
+                                    .child(action.label.clone())
+                                    .on_click(move |_, window, cx| {
+                                        handler(window, cx);
+                                        if let Some(on_close) = on_close.as_ref() {
+                                            on_close(window, cx);
+                                        }
+                                    })
+                            })),
+                    )
+                }),
+        )
+        .when_some(on_close, |this, on_close| {
+            this.child(
+                Button::new("toast-close")
+                    .flex_none()
+                    .rounded(theme.radii.sm)
+                    .hover(theme.selection)
+                    .child(svg().size_4().text_color(theme.text).path("close"))
+                    .on_click(move |_, window, cx| on_close(window, cx)),
+            )
+        })
+}
+
+/// Draws the visible toasts stacked in the configured corner of the window,
+/// the newest one closest to the corner.
+#[derive(IntoElement, Default)]
+pub struct NotificationStack;
+
+impl NotificationStack {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl RenderOnce for NotificationStack {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let Some(notifications) = cx.try_global::<Notifications>() else {
+            return div().into_any_element();
+        };
+        if notifications.active.is_empty() {
+            return div().into_any_element();
+        }
+
+        let corner = notifications.corner;
+        let viewport = Bounds::new(point(px(0.0), px(0.0)), window.viewport_size());
+        let top = matches!(corner, Corner::TopLeft | Corner::TopRight);
+        let mut toasts = notifications
+            .active
+            .iter()
+            .take(MAX_VISIBLE)
+            .map(|active| {
+                let id = active.id;
+                render_toast(
+                    ElementId::NamedInteger("toast".into(), id.0 as u64),
+                    &active.toast,
+                    Some(Rc::new(move |_, cx| Notifications::dismiss(id, cx))),
+                    cx,
+                )
+                .w(px(360.0))
+                .occlude()
+                .on_hover(move |hovered, _, cx| Notifications::set_hovered(id, *hovered, cx))
+            })
+            .collect::<Vec<_>>();
+        if top {
+            toasts.reverse();
+        }
+
+        deferred(
+            anchored()
+                .anchor(corner)
+                .position(viewport.corner(corner))
+                .child(v_flex().m_4().gap_2().children(toasts)),
+        )
+        .with_priority(4)
+        .into_any_element()
+    }
+}
+
+/// List of dismissed notifications, most recent first.
+#[derive(IntoElement, Default)]
+pub struct NotificationHistory;
+
+impl NotificationHistory {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl RenderOnce for NotificationHistory {
+    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        let history = Notifications::history(cx);
+        let theme = cx.theme();
+
+        v_flex()
+            .gap_2()
+            .text_color(theme.text)
+            .child(
+                h_flex()
+                    .justify_between()
+                    .items_center()
+                    .child(
+                        div()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .child("Notifications"),
+                    )
+                    .when(!history.is_empty(), |this| {
+                        this.child(
+                            Button::new("notification-history-clear")
+                                .px(theme.spacing.md)
+                                .rounded(theme.radii.sm)
+                                .hover(theme.selection)
+                                .child("Clear")
+                                .on_click(|_, _, cx| Notifications::clear_history(cx)),
+                        )
+                    }),
+            )
+            .when(history.is_empty(), |this| {
+                this.child(div().text_color(theme.muted).child("No notifications"))
+            })
+            .child(
+                v_flex()
+                    .id("notification-history")
+                    .gap_2()
+                    .overflow_y_scroll()
+                    .children(history.iter().enumerate().map(|(ix, toast)| {
+                        render_toast(
+                            ElementId::NamedInteger("history".into(), ix as u64),
+                            toast,
+                            None,
+                            cx,
+                        )
+                    })),
+            )
+    }
+}
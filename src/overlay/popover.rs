@@ -0,0 +1,548 @@
+use std::{cell::Cell, rc::Rc};
+
+use crate::overlay::menu_builder::Cancel;
+use crate::prelude::*;
+use gpui::{prelude::*, *};
+
+actions!(
+    popover,
+    [
+        /// Moves focus to the next element, staying inside the popover.
+        FocusNext,
+        /// Moves focus to the previous element, staying inside the popover.
+        FocusPrevious
+    ]
+);
+
+/// Distance between the trigger and the popover, also the arrow's height.
+const ARROW_SIZE: Pixels = px(6.0);
+/// Minimum distance kept to the window edges.
+const WINDOW_MARGIN: Pixels = px(8.0);
+
+pub fn init(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("escape", Cancel, Some("Popover")),
+        KeyBinding::new("tab", FocusNext, Some("Popover")),
+        KeyBinding::new("shift-tab", FocusPrevious, Some("Popover")),
+    ]);
+}
+
+/// Moves focus to the next or previous tab stop within `container`. Past
+/// the last one it wraps to the first, and the other way around.
+fn cycle_focus(container: &FocusHandle, forward: bool, window: &mut Window, cx: &mut App) {
+    let step = |window: &mut Window| {
+        if forward {
+            window.focus_next()
+        } else {
+            window.focus_prev()
+        }
+    };
+    step(window);
+    // Once focus left, keep going around the tab order of the window until
+    // it enters the container again at its first or last tab stop.
+    let left = window.focused(cx);
+    while !container.contains_focused(window, cx) {
+        step(window);
+        if window.focused(cx) == left {
+            // Nothing inside takes focus.
+            window.focus(container);
+            break;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Side {
+    Top,
+    #[default]
+    Bottom,
+    Left,
+    Right,
+}
+
+impl Side {
+    fn flipped(self) -> Self {
+        match self {
+            Self::Top => Self::Bottom,
+            Self::Bottom => Self::Top,
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
+}
+
+/// Alignment of the popover along the trigger's edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Align {
+    Start,
+    #[default]
+    Center,
+    End,
+}
+
+struct PopoverState {
+    open: Cell<bool>,
+    was_open: Cell<bool>,
+    focus: FocusHandle,
+    previous_focus: Cell<Option<FocusHandle>>,
+    trigger_bounds: Cell<Option<Bounds<Pixels>>>,
+    content_bounds: Cell<Option<Bounds<Pixels>>>,
+}
+
+/// Shared open state of a [`Popover`], which lets code outside the popover
+/// open or close it, e.g. after a validation error.
+#[derive(Clone)]
+pub struct PopoverHandle(Rc<PopoverState>);
+
+impl PopoverHandle {
+    pub fn new(cx: &mut App) -> Self {
+        Self(Rc::new(PopoverState {
+            open: Cell::new(false),
+            was_open: Cell::new(false),
+            focus: cx.focus_handle(),
+            previous_focus: Cell::new(None),
+            trigger_bounds: Cell::new(None),
+            content_bounds: Cell::new(None),
+        }))
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.0.open.get()
+    }
+
+    pub fn set_open(&self, open: bool, window: &mut Window) {
+        if self.0.open.replace(open) != open {
+            window.refresh();
+        }
+    }
+
+    pub fn open(&self, window: &mut Window) {
+        self.set_open(true, window);
+    }
+
+    pub fn close(&self, window: &mut Window) {
+        self.set_open(false, window);
+    }
+
+    pub fn toggle(&self, window: &mut Window) {
+        self.set_open(!self.is_open(), window);
+    }
+}
+
+/// Floating content attached to a trigger element.
+///
+/// The open state is either owned by the popover, shared through a
+/// [`PopoverHandle`] or fully controlled by the parent with [`Popover::open`]
+/// and [`Popover::on_open_change`]. While open, the focus is moved into the
+/// popover and kept there until it closes.
+#[derive(IntoElement)]
+pub struct Popover {
+    id: ElementId,
+    handle: Option<PopoverHandle>,
+    open: Option<bool>,
+    trigger: Option<AnyElement>,
+    content: Option<Box<dyn Fn(&mut Window, &mut App) -> AnyElement>>,
+    on_open_change: Option<Rc<dyn Fn(bool, &mut Window, &mut App)>>,
+    side: Side,
+    align: Align,
+    arrow: bool,
+    close_on_click_outside: bool,
+    close_on_escape: bool,
+}
+
+impl Popover {
+    pub fn new(id: impl Into<ElementId>) -> Self {
+        Self {
+            id: id.into(),
+            handle: None,
+            open: None,
+            trigger: None,
+            content: None,
+            on_open_change: None,
+            side: Side::default(),
+            align: Align::default(),
+            arrow: true,
+            close_on_click_outside: true,
+            close_on_escape: true,
+        }
+    }
+
+    /// Shares the open state with `handle` instead of keeping it internally.
+    pub fn handle(self, handle: &PopoverHandle) -> Self {
+        Self {
+            handle: Some(handle.clone()),
+            ..self
+        }
+    }
+
+    /// Controls the open state. Pair with [`Popover::on_open_change`] to
+    /// learn about the popover requesting to open or close itself.
+    pub fn open(self, open: bool) -> Self {
+        Self {
+            open: Some(open),
+            ..self
+        }
+    }
+
+    /// Called when the trigger is clicked or the popover is dismissed.
+    pub fn on_open_change(self, handler: impl Fn(bool, &mut Window, &mut App) + 'static) -> Self {
+        Self {
+            on_open_change: Some(Rc::new(handler)),
+            ..self
+        }
+    }
+
+    pub fn trigger(self, trigger: impl IntoElement) -> Self {
+        Self {
+            trigger: Some(trigger.into_any_element()),
+            ..self
+        }
+    }
+
+    pub fn content<E: IntoElement>(
+        self,
+        content: impl Fn(&mut Window, &mut App) -> E + 'static,
+    ) -> Self {
+        Self {
+            content: Some(Box::new(move |window, cx| {
+                content(window, cx).into_any_element()
+            })),
+            ..self
+        }
+    }
+
+    /// Preferred side of the trigger. The popover flips to the opposite side
+    /// if it does not fit into the window.
+    pub fn side(self, side: Side) -> Self {
+        Self { side, ..self }
+    }
+
+    pub fn align(self, align: Align) -> Self {
+        Self { align, ..self }
+    }
+
+    pub fn arrow(self, arrow: bool) -> Self {
+        Self { arrow, ..self }
+    }
+
+    pub fn close_on_click_outside(self, close: bool) -> Self {
+        Self {
+            close_on_click_outside: close,
+            ..self
+        }
+    }
+
+    pub fn close_on_escape(self, close: bool) -> Self {
+        Self {
+            close_on_escape: close,
+            ..self
+        }
+    }
+}
+
+/// Returns the side the popover ends up on and its origin.
+fn place(
+    trigger: Bounds<Pixels>,
+    size: Size<Pixels>,
+    side: Side,
+    align: Align,
+    viewport: Size<Pixels>,
+) -> (Side, Point<Pixels>) {
+    let fits = |side: Side| match side {
+        Side::Top => trigger.top() - ARROW_SIZE - size.height >= WINDOW_MARGIN,
+        Side::Bottom => {
+            trigger.bottom() + ARROW_SIZE + size.height <= viewport.height - WINDOW_MARGIN
+        }
+        Side::Left => trigger.left() - ARROW_SIZE - size.width >= WINDOW_MARGIN,
+        Side::Right => trigger.right() + ARROW_SIZE + size.width <= viewport.width - WINDOW_MARGIN,
+    };
+    let side = if !fits(side) && fits(side.flipped()) {
+        side.flipped()
+    } else {
+        side
+    };
+
+    let aligned = |start: Pixels, end: Pixels, len: Pixels, max: Pixels| {
+        let position = match align {
+            Align::Start => start,
+            Align::Center => (start + end - len) / 2.0,
+            Align::End => end - len,
+        };
+        position.min(max - len - WINDOW_MARGIN).max(WINDOW_MARGIN)
+    };
+
+    let origin = match side {
+        Side::Top => point(
+            aligned(trigger.left(), trigger.right(), size.width, viewport.width),
+            trigger.top() - ARROW_SIZE - size.height,
+        ),
+        Side::Bottom => point(
+            aligned(trigger.left(), trigger.right(), size.width, viewport.width),
+            trigger.bottom() + ARROW_SIZE,
+        ),
+        Side::Left => point(
+            trigger.left() - ARROW_SIZE - size.width,
+            aligned(
+                trigger.top(),
+                trigger.bottom(),
+                size.height,
+                viewport.height,
+            ),
+        ),
+        Side::Right => point(
+            trigger.right() + ARROW_SIZE,
+            aligned(
+                trigger.top(),
+                trigger.bottom(),
+                size.height,
+                viewport.height,
+            ),
+        ),
+    };
+    (side, origin)
+}
+
+/// Paints a triangle on the `side` edge of `content` pointing at the center
+/// of `trigger`.
+fn paint_arrow(
+    side: Side,
+    trigger: Bounds<Pixels>,
+    content: Bounds<Pixels>,
+    size: Pixels,
+    color: Hsla,
+    window: &mut Window,
+) {
+    // Keep the arrow clear of the rounded corners.
+    let inset = size + px(8.0);
+    let tip = match side {
+        Side::Top | Side::Bottom => {
+            let x = trigger
+                .center()
+                .x
+                .clamp(content.left() + inset, content.right() - inset);
+            if side == Side::Bottom {
+                point(x, content.top() - size)
+            } else {
+                point(x, content.bottom() + size)
+            }
+        }
+        Side::Left | Side::Right => {
+            let y = trigger
+                .center()
+                .y
+                .clamp(content.top() + inset, content.bottom() - inset);
+            if side == Side::Right {
+                point(content.left() - size, y)
+            } else {
+                point(content.right() + size, y)
+            }
+        }
+    };
+    let (a, b) = match side {
+        Side::Bottom => (
+            point(tip.x - size, tip.y + size),
+            point(tip.x + size, tip.y + size),
+        ),
+        Side::Top => (
+            point(tip.x - size, tip.y - size),
+            point(tip.x + size, tip.y - size),
+        ),
+        Side::Right => (
+            point(tip.x + size, tip.y - size),
+            point(tip.x + size, tip.y + size),
+        ),
+        Side::Left => (
+            point(tip.x - size, tip.y - size),
+            point(tip.x - size, tip.y + size),
+        ),
+    };
+
+    let mut path = PathBuilder::fill();
+    path.move_to(tip);
+    path.line_to(a);
+    path.line_to(b);
+    path.close();
+    if let Ok(path) = path.build() {
+        window.paint_path(path, color);
+    }
+}
+
+impl RenderOnce for Popover {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let handle = self.handle.clone().unwrap_or_else(|| {
+            window
+                .use_keyed_state(self.id.clone(), cx, |_, cx| PopoverHandle::new(cx))
+                .read(cx)
+                .clone()
+        });
+        if let Some(open) = self.open {
+            handle.0.open.set(open);
+        }
+        let state = handle.0.clone();
+        let open = state.open.get();
+
+        // Move the focus into the popover when it opens and give it back when
+        // it closes while still holding it.
+        if open != state.was_open.replace(open) {
+            let state = state.clone();
+            window.defer(cx, move |window, cx| {
+                if open {
+                    state.previous_focus.set(window.focused(cx));
+                    window.focus(&state.focus);
+                } else {
+                    if let Some(previous) = state.previous_focus.take()
+                        && (state.focus.contains_focused(window, cx)
+                            || window.focused(cx).is_none())
+                    {
+                        window.focus(&previous);
+                    }
+                    state.content_bounds.set(None);
+                }
+            });
+        }
+
+        // Closes the popover unless its open state is controlled by the parent.
+        let request_open = {
+            let handle = handle.clone();
+            let on_open_change = self.on_open_change.clone();
+            let controlled = self.open.is_some();
+            move |open: bool, window: &mut Window, cx: &mut App| {
+                if !controlled {
+                    handle.set_open(open, window);
+                }
+                if let Some(on_open_change) = on_open_change.as_ref() {
+                    on_open_change(open, window, cx);
+                }
+            }
+        };
+        let request_open = Rc::new(request_open);
+
+        let trigger_state = state.clone();
+        let trigger = div()
+            .id(self.id.clone())
+            .relative()
+            .child(
+                canvas(
+                    move |bounds, _, _| trigger_state.trigger_bounds.set(Some(bounds)),
+                    |_, _, _, _| {},
+                )
+                .absolute()
+                .size_full(),
+            )
+            .children(self.trigger)
+            .on_click({
+                let request_open = request_open.clone();
+                move |_, window, cx| request_open(!open, window, cx)
+            });
+
+        let popover = open
+            .then(|| state.trigger_bounds.get().zip(self.content))
+            .flatten()
+            .map(|(trigger_bounds, content)| {
+                let measured = state.content_bounds.get();
+                let size = measured.map(|bounds| bounds.size).unwrap_or_default();
+                let (side, origin) = place(
+                    trigger_bounds,
+                    size,
+                    self.side,
+                    self.align,
+                    window.viewport_size(),
+                );
+                let background = cx.theme().foreground;
+                let border = cx.theme().border;
+                let arrow = self.arrow;
+                let close_on_click_outside = self.close_on_click_outside;
+                let close_on_escape = self.close_on_escape;
+                let content_state = state.clone();
+                let focus_state = state.clone();
+
+                deferred(
+                    anchored().position(origin).child(
+                        div()
+                            .id("popover-content")
+                            .occlude()
+                            .track_focus(&state.focus)
+                            .key_context("Popover")
+                            // The first frame only measures the content.
+                            .when(measured.is_none(), |this| this.invisible())
+                            .bg(background)
+                            .border_1()
+                            .border_color(border)
+                            .rounded(cx.theme().radii.lg)
+                            .shadow(box_shadows(&cx.theme().shadows.lg))
+                            .p_2()
+                            .text_color(cx.theme().text)
+                            .on_action({
+                                let request_open = request_open.clone();
+                                move |_: &Cancel, window, cx| {
+                                    if close_on_escape {
+                                        request_open(false, window, cx);
+                                    } else {
+                                        cx.propagate();
+                                    }
+                                }
+                            })
+                            .on_action(move |_: &FocusNext, window, cx| {
+                                cycle_focus(&focus_state.focus, true, window, cx)
+                            })
+                            .on_action({
+                                let state = state.clone();
+                                move |_: &FocusPrevious, window, cx| {
+                                    cycle_focus(&state.focus, false, window, cx)
+                                }
+                            })
+                            .on_mouse_down_out({
+                                let request_open = request_open.clone();
+                                move |event, window, cx| {
+                                    // Clicks on the trigger toggle the popover themselves.
+                                    if close_on_click_outside
+                                        && !trigger_bounds.contains(&event.position)
+                                    {
+                                        request_open(false, window, cx);
+                                    }
+                                }
+                            })
+                            .child(
+                                canvas(
+                                    move |bounds, _, _| {
+                                        content_state.content_bounds.set(Some(bounds))
+                                    },
+                                    move |bounds, _, window, _| {
+                                        if arrow {
+                                            paint_arrow(
+                                                side,
+                                                trigger_bounds,
+                                                bounds,
+                                                ARROW_SIZE + px(1.0),
+                                                border,
+                                                window,
+                                            );
+                                            paint_arrow(
+                                                side,
+                                                trigger_bounds,
+                                                bounds,
+                                                ARROW_SIZE,
+                                                background,
+                                                window,
+                                            );
+                                        }
+                                    },
+                                )
+                                .absolute()
+                                .size_full(),
+                            )
+                            .child(content(window, cx)),
+                    ),
+                )
+                .with_priority(1)
+            });
+
+        // Draw again once the measuring frame is done, refreshing doesn't
+        // work while drawing.
+        if open && state.content_bounds.get().is_none() {
+            window.request_animation_frame();
+        }
+
+        trigger.children(popover)
+    }
+}
@@ -22,11 +22,16 @@ impl Tooltip {
                 .offset(point(px(2.0), px(8.0)))
                 .child(
                     div()
-                        .bg(cx.theme().muted_background)
+                        .bg(cx.theme().tooltip().background)
                         //.border_2()
                         //.border_color(cx.theme().border)
-                        .rounded_lg()
-                        .child(v_flex().py_1().px_2().child(self.text)),
+                        .rounded(cx.theme().tooltip().radius)
+                        .child(
+                            v_flex()
+                                .py(cx.theme().spacing.sm)
+                                .px(cx.theme().spacing.md)
+                                .child(self.text),
+                        ),
                 ),
         )
         .with_priority(1)
@@ -38,23 +43,27 @@ where
     C: AppContext + Borrow<App>,
 {
     let app = (*cx).borrow();
-    let theme = Theme::global(app);
+    let theme = app.theme();
+    let style = theme.tooltip();
 
     // padding to avoid tooltip appearing right below the mouse cursor
-    div().pl_2().pt_2p5().child(
-        v_flex()
-            .bg(theme.secondary)
-            .rounded_lg()
-            .border_1()
-            .border_color(theme.border)
-            //.shadow(index.shadow(cx))
-            //.font(ui_font)
-            //.text_ui(app)
-            .text_color(theme.text)
-            .py_1()
-            .px_2()
-            .map(|el| f(el, cx)),
-    )
+    div()
+        .pl(theme.spacing.md)
+        .pt(theme.spacing.md + theme.spacing.xs)
+        .child(
+            theme
+                .typography
+                .apply(v_flex())
+                .bg(style.background)
+                .rounded(style.radius)
+                .border_1()
+                .border_color(style.border)
+                .shadow(box_shadows(&theme.shadows.md))
+                .text_color(style.text)
+                .py(theme.spacing.sm)
+                .px(theme.spacing.md)
+                .map(|el| f(el, cx)),
+        )
 }
 
 impl Render for Tooltip {
@@ -0,0 +1,509 @@
+use std::{
+    cell::Cell,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use crate::overlay::menu_builder::{
+    Cancel, Confirm, SelectFirst, SelectLast, SelectNext, SelectPrevious,
+};
+use crate::prelude::*;
+use gpui::{prelude::*, *};
+
+/// Time after which typed characters start a new type-to-select query.
+const TYPE_AHEAD_TIMEOUT: Duration = Duration::from_millis(1000);
+/// Maximum height of the option list before it starts scrolling.
+const MAX_LIST_HEIGHT: Pixels = px(240.0);
+/// Height assumed for an option row before the list was laid out once.
+const ESTIMATED_ROW_HEIGHT: Pixels = px(28.0);
+
+pub fn init(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("escape", Cancel, Some("Select")),
+        KeyBinding::new("enter", Confirm, Some("Select")),
+        KeyBinding::new("space", Confirm, Some("Select")),
+        KeyBinding::new("up", SelectPrevious, Some("Select")),
+        KeyBinding::new("down", SelectNext, Some("Select")),
+        KeyBinding::new("home", SelectFirst, Some("Select")),
+        KeyBinding::new("end", SelectLast, Some("Select")),
+    ]);
+}
+
+pub struct SelectOption<T> {
+    pub value: T,
+    pub label: SharedString,
+}
+
+/// Dropdown showing the current value, which opens a list of options when
+/// clicked or confirmed with the keyboard.
+///
+/// In single mode choosing an option replaces the value and closes the list.
+/// In [`Select::multiple`] mode options are toggled and the list stays open.
+pub struct Select<T: Clone + PartialEq + 'static> {
+    focus: FocusHandle,
+    options: Vec<SelectOption<T>>,
+    /// Indices into `options`, in the order they were selected.
+    selected: Vec<usize>,
+    multiple: bool,
+    placeholder: SharedString,
+    open: bool,
+    highlighted: Option<usize>,
+    type_ahead: String,
+    type_ahead_at: Option<Instant>,
+    scroll_handle: ScrollHandle,
+    trigger_bounds: Rc<Cell<Option<Bounds<Pixels>>>>,
+    list_bounds: Rc<Cell<Option<Bounds<Pixels>>>>,
+    on_change: Option<Rc<dyn Fn(&[T], &mut Window, &mut App)>>,
+}
+
+impl<T: Clone + PartialEq + 'static> Select<T> {
+    pub fn new(
+        options: impl IntoIterator<Item = (T, impl Into<SharedString>)>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        Self {
+            focus: cx.focus_handle(),
+            options: options
+                .into_iter()
+                .map(|(value, label)| SelectOption {
+                    value,
+                    label: label.into(),
+                })
+                .collect(),
+            selected: Vec::new(),
+            multiple: false,
+            placeholder: "Select...".into(),
+            open: false,
+            highlighted: None,
+            type_ahead: String::new(),
+            type_ahead_at: None,
+            scroll_handle: ScrollHandle::new(),
+            trigger_bounds: Rc::new(Cell::new(None)),
+            list_bounds: Rc::new(Cell::new(None)),
+            on_change: None,
+        }
+    }
+
+    /// Allows selecting any number of options.
+    pub fn multiple(self) -> Self {
+        Self {
+            multiple: true,
+            ..self
+        }
+    }
+
+    pub fn placeholder(self, placeholder: impl Into<SharedString>) -> Self {
+        Self {
+            placeholder: placeholder.into(),
+            ..self
+        }
+    }
+
+    /// Selects the option holding `value`, if there is one.
+    pub fn selected(mut self, value: &T) -> Self {
+        self.set_selected([value]);
+        self
+    }
+
+    /// Called with all selected values whenever the user changes the
+    /// selection. In single mode the slice holds at most one value.
+    pub fn on_change(self, handler: impl Fn(&[T], &mut Window, &mut App) + 'static) -> Self {
+        Self {
+            on_change: Some(Rc::new(handler)),
+            ..self
+        }
+    }
+
+    /// The selected value in single mode or the first selected one otherwise.
+    pub fn value(&self) -> Option<&T> {
+        self.selected.first().map(|ix| &self.options[*ix].value)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.selected.iter().map(|ix| &self.options[*ix].value)
+    }
+
+    /// Replaces the selection without calling `on_change`. Values without a
+    /// matching option are ignored.
+    pub fn set_selected<'a>(&mut self, values: impl IntoIterator<Item = &'a T>) {
+        self.selected.clear();
+        for value in values {
+            if let Some(ix) = self.options.iter().position(|o| o.value == *value) {
+                if !self.multiple {
+                    self.selected.clear();
+                }
+                if !self.selected.contains(&ix) {
+                    self.selected.push(ix);
+                }
+            }
+        }
+    }
+
+    pub fn set_options(
+        &mut self,
+        options: impl IntoIterator<Item = (T, impl Into<SharedString>)>,
+        cx: &mut Context<Self>,
+    ) {
+        let selected = self.values().cloned().collect::<Vec<_>>();
+        self.options = options
+            .into_iter()
+            .map(|(value, label)| SelectOption {
+                value,
+                label: label.into(),
+            })
+            .collect();
+        self.set_selected(&selected);
+        self.highlighted = None;
+        cx.notify();
+    }
+
+    fn set_open(&mut self, open: bool, cx: &mut Context<Self>) {
+        self.open = open;
+        if open {
+            self.highlighted = self.selected.first().copied().or(Some(0));
+            if let Some(ix) = self.highlighted {
+                self.scroll_handle.scroll_to_item(ix);
+            }
+        }
+        cx.notify();
+    }
+
+    fn choose(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        if self.multiple {
+            match self.selected.iter().position(|selected| *selected == ix) {
+                Some(position) => _ = self.selected.remove(position),
+                None => self.selected.push(ix),
+            }
+        } else {
+            let unchanged = self.selected == [ix];
+            self.set_open(false, cx);
+            if unchanged {
+                return;
+            }
+            self.selected = vec![ix];
+        }
+        self.highlighted = Some(ix);
+        cx.notify();
+
+        if let Some(on_change) = self.on_change.clone() {
+            let values = self.values().cloned().collect::<Vec<_>>();
+            window.defer(cx, move |window, cx| on_change(&values, window, cx));
+        }
+    }
+
+    fn highlight(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.highlighted = Some(ix);
+        self.scroll_handle.scroll_to_item(ix);
+        cx.notify();
+    }
+
+    /// Moves the highlight while open, or the selection itself when a single
+    /// select is closed.
+    fn step(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        if self.open {
+            self.highlight(ix, cx);
+        } else if !self.multiple {
+            self.choose(ix, window, cx);
+        } else {
+            self.set_open(true, cx);
+        }
+    }
+
+    fn current(&self) -> Option<usize> {
+        if self.open {
+            self.highlighted
+        } else {
+            self.selected.first().copied()
+        }
+    }
+
+    fn select_next(&mut self, _: &SelectNext, window: &mut Window, cx: &mut Context<Self>) {
+        if self.options.is_empty() {
+            return;
+        }
+        let next = self
+            .current()
+            .map_or(0, |ix| (ix + 1).min(self.options.len() - 1));
+        self.step(next, window, cx);
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, window: &mut Window, cx: &mut Context<Self>) {
+        if self.options.is_empty() {
+            return;
+        }
+        let previous = self.current().map_or(0, |ix| ix.saturating_sub(1));
+        self.step(previous, window, cx);
+    }
+
+    fn select_first(&mut self, _: &SelectFirst, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.options.is_empty() {
+            self.step(0, window, cx);
+        }
+    }
+
+    fn select_last(&mut self, _: &SelectLast, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.options.is_empty() {
+            self.step(self.options.len() - 1, window, cx);
+        }
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        match self.highlighted {
+            Some(ix) if self.open => self.choose(ix, window, cx),
+            _ => self.set_open(!self.open, cx),
+        }
+    }
+
+    fn cancel(&mut self, _: &Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        if self.open {
+            self.set_open(false, cx);
+        } else {
+            cx.propagate();
+        }
+    }
+
+    fn type_ahead(&mut self, event: &KeyDownEvent, window: &mut Window, cx: &mut Context<Self>) {
+        let modifiers = event.keystroke.modifiers;
+        if modifiers.control || modifiers.alt || modifiers.platform {
+            return;
+        }
+        let Some(text) = event.keystroke.key_char.as_ref() else {
+            return;
+        };
+        if text.chars().any(char::is_control) || (text == " " && self.type_ahead.is_empty()) {
+            return;
+        }
+        if self
+            .type_ahead_at
+            .is_none_or(|at| at.elapsed() > TYPE_AHEAD_TIMEOUT)
+        {
+            self.type_ahead.clear();
+        }
+        self.type_ahead.push_str(text);
+        self.type_ahead_at = Some(Instant::now());
+
+        let query = self.type_ahead.to_lowercase();
+        let start = self.current().map_or(0, |ix| ix + 1);
+        // Repeating the same character cycles through the options starting
+        // with it, like native select boxes do.
+        let repeated = query.chars().all(|c| query.starts_with(c));
+        let prefix = if repeated {
+            query.chars().take(1).collect()
+        } else {
+            query.clone()
+        };
+        let found = (start..self.options.len())
+            .chain(0..start)
+            .find(|ix| self.options[*ix].label.to_lowercase().starts_with(&prefix));
+        let found = match (found, self.current()) {
+            // Keep the current option while it still matches a longer query.
+            (_, Some(current))
+                if !repeated
+                    && self.options[current]
+                        .label
+                        .to_lowercase()
+                        .starts_with(&prefix) =>
+            {
+                Some(current)
+            }
+            (found, _) => found,
+        };
+        if let Some(ix) = found {
+            self.step(ix, window, cx);
+        }
+        cx.stop_propagation();
+    }
+
+    fn render_value(&self, cx: &App) -> AnyElement {
+        if self.selected.is_empty() {
+            return div()
+                .text_color(cx.theme().muted)
+                .child(self.placeholder.clone())
+                .into_any_element();
+        }
+        let labels = self
+            .selected
+            .iter()
+            .map(|ix| self.options[*ix].label.as_ref())
+            .collect::<Vec<_>>()
+            .join(", ");
+        div()
+            .overflow_hidden()
+            .text_ellipsis()
+            .whitespace_nowrap()
+            .child(labels)
+            .into_any_element()
+    }
+
+    fn render_list(&self, window: &Window, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let trigger = self.trigger_bounds.get()?;
+        let height = self
+            .list_bounds
+            .get()
+            .map(|bounds| bounds.size.height)
+            .unwrap_or((ESTIMATED_ROW_HEIGHT * self.options.len() as f32).min(MAX_LIST_HEIGHT));
+        // Open upwards when the list would not fit below the trigger, but
+        // does above it.
+        let above = trigger.bottom() + height > window.viewport_size().height
+            && trigger.top() - height >= px(0.0);
+        let (anchor, position) = if above {
+            (Corner::BottomLeft, trigger.origin)
+        } else {
+            (Corner::TopLeft, trigger.bottom_left())
+        };
+
+        let list_bounds = self.list_bounds.clone();
+        let trigger_bounds = self.trigger_bounds.clone();
+        let selection = cx.theme().selection;
+
+        Some(
+            deferred(
+                anchored()
+                    .anchor(anchor)
+                    .position(position)
+                    .snap_to_window_with_margin(px(8.0))
+                    .child(
+                        v_flex()
+                            .id("select-options")
+                            .occlude()
+                            .min_w(trigger.size.width)
+                            .max_h(MAX_LIST_HEIGHT)
+                            .overflow_y_scroll()
+                            .track_scroll(&self.scroll_handle)
+                            .when(above, |this| this.mb_1())
+                            .when(!above, |this| this.mt_1())
+                            .p_1()
+                            .gap_0p5()
+                            .bg(cx.theme().foreground)
+                            .border_1()
+                            .border_color(cx.theme().border)
+                            .rounded(cx.theme().radii.lg)
+                            .shadow(box_shadows(&cx.theme().shadows.md))
+                            .text_color(cx.theme().text)
+                            .on_mouse_down_out(cx.listener(
+                                move |this, event: &MouseDownEvent, _, cx| {
+                                    // Clicks on the trigger toggle the list themselves.
+                                    if trigger_bounds
+                                        .get()
+                                        .is_none_or(|bounds| !bounds.contains(&event.position))
+                                    {
+                                        this.set_open(false, cx);
+                                    }
+                                },
+                            ))
+                            .child(
+                                canvas(
+                                    move |bounds, _, _| list_bounds.set(Some(bounds)),
+                                    |_, _, _, _| {},
+                                )
+                                .absolute()
+                                .size_full(),
+                            )
+                            .children(self.options.iter().enumerate().map(|(ix, option)| {
+                                let selected = self.selected.contains(&ix);
+                                Button::new(("select-option", ix))
+                                    .hover(selection)
+                                    .when(self.highlighted == Some(ix), |this| this.base(selection))
+                                    .child(
+                                        h_flex()
+                                            .px(cx.theme().spacing.md)
+                                            .py_0p5()
+                                            .gap_2()
+                                            .items_center()
+                                            .child(div().size_4().flex_none().when(
+                                                selected,
+                                                |this| {
+                                                    this.child(
+                                                        svg()
+                                                            .text_color(cx.theme().text)
+                                                            .size_full()
+                                                            .path("checkbox-inner"),
+                                                    )
+                                                },
+                                            ))
+                                            .child(option.label.clone()),
+                                    )
+                                    .on_mouse_move(cx.listener(move |this, _, _, cx| {
+                                        if this.highlighted != Some(ix) {
+                                            this.highlighted = Some(ix);
+                                            cx.notify();
+                                        }
+                                    }))
+                                    .on_mouse_down(
+                                        MouseButton::Left,
+                                        cx.listener(move |this, _, window, cx| {
+                                            cx.stop_propagation();
+                                            this.choose(ix, window, cx);
+                                        }),
+                                    )
+                            })),
+                    ),
+            )
+            .with_priority(1),
+        )
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> Focusable for Select<T> {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus.clone()
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> Render for Select<T> {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let trigger_bounds = self.trigger_bounds.clone();
+        let list = if self.open {
+            self.render_list(window, cx)
+        } else {
+            self.list_bounds.set(None);
+            None
+        };
+
+        h_flex()
+            .id("select")
+            .track_focus(&self.focus)
+            .key_context("Select")
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .on_key_down(cx.listener(Self::type_ahead))
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(|this, _, window, cx| {
+                    window.focus(&this.focus);
+                    this.set_open(!this.open, cx);
+                }),
+            )
+            .relative()
+            .gap_2()
+            .px(cx.theme().spacing.md)
+            .py_0p5()
+            .justify_between()
+            .items_center()
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded(cx.theme().radii.lg)
+            .text_color(cx.theme().text)
+            .child(
+                canvas(
+                    move |bounds, _, _| trigger_bounds.set(Some(bounds)),
+                    |_, _, _, _| {},
+                )
+                .absolute()
+                .size_full(),
+            )
+            .child(self.render_value(cx))
+            .child(
+                svg()
+                    .flex_none()
+                    .size_4()
+                    .text_color(cx.theme().text)
+                    .path("chevron_up_down"),
+            )
+            .children(list)
+    }
+}
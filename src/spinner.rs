@@ -8,8 +8,6 @@ pub struct Spinner {
     value: SharedString,
     on_inc: Box<dyn Fn(&MouseDownEvent, &mut Window, &mut App)>,
     on_dec: Box<dyn Fn(&MouseDownEvent, &mut Window, &mut App)>,
-    inc_el: AnyElement,
-    dec_el: AnyElement,
 }
 
 impl Styled for Spinner {
@@ -47,18 +45,6 @@ impl Spinner {
             value: value.into(),
             on_inc: Box::new(on_inc),
             on_dec: Box::new(on_dec),
-            inc_el: svg()
-                .text_color(gpui::white())
-                .path("chevron_up")
-                .flex_none()
-                .size_full()
-                .into_any_element(), //"+".into_any_element(),
-            dec_el: svg()
-                .text_color(gpui::white())
-                .path("chevron_down")
-                .flex_none()
-                .size_full()
-                .into_any_element(), //"-".into_any_element(),
         }
     }
 }
@@ -66,6 +52,8 @@ impl Spinner {
 impl RenderOnce for Spinner {
     fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
         let size = window.text_style().font_size.to_pixels(window.rem_size()) * 2.0;
+        let text = cx.theme().text;
+        let icon = |path: &'static str| svg().text_color(text).path(path).flex_none().size_full();
         self.base
             .id(self.id.clone())
             .flex()
@@ -81,7 +69,7 @@ impl RenderOnce for Spinner {
                     .h_full()
                     .w(size)
                     .on_mouse_down(MouseButton::Left, self.on_dec)
-                    .child(self.dec_el),
+                    .child(icon("chevron_down")),
             )
             .child(
                 h_flex()
@@ -97,7 +85,7 @@ impl RenderOnce for Spinner {
                     .h_full()
                     .w(size)
                     .on_mouse_down(MouseButton::Left, self.on_inc)
-                    .child(self.inc_el),
+                    .child(icon("chevron_up")),
             )
     }
 }
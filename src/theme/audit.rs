@@ -0,0 +1,206 @@
+//! WCAG contrast checks of the color pairs the components draw.
+
+use gpui::*;
+
+use super::Theme;
+
+/// Contrast WCAG AA asks for normal sized text.
+pub const AA_CONTRAST: f32 = 4.5;
+
+/// Rounds of fixes [`Theme::fix_contrast`] makes, as fixing one pair can
+/// break another sharing a color.
+const MAX_FIX_ROUNDS: usize = 4;
+
+/// A text color and the background the components draw it on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContrastPair {
+    TextOnBackground,
+    /// Selected tabs and highlighted menu entries.
+    TextOnSelection,
+    /// Empty text inputs.
+    PlaceholderOnBackground,
+    /// Menus and popovers.
+    MenuText,
+    TooltipText,
+}
+
+impl ContrastPair {
+    pub const ALL: [Self; 5] = [
+        Self::TextOnBackground,
+        Self::TextOnSelection,
+        Self::PlaceholderOnBackground,
+        Self::MenuText,
+        Self::TooltipText,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::TextOnBackground => "text on background",
+            Self::TextOnSelection => "text on selection",
+            Self::PlaceholderOnBackground => "placeholder on background",
+            Self::MenuText => "menu text on menu background",
+            Self::TooltipText => "tooltip text on tooltip background",
+        }
+    }
+
+    /// The text and background color, with translucent colors blended onto
+    /// what is behind them.
+    fn colors(self, theme: &Theme) -> (Hsla, Hsla) {
+        let (text, background) = match self {
+            Self::TextOnBackground => (theme.text, theme.background),
+            Self::TextOnSelection => (theme.text, theme.selection),
+            Self::PlaceholderOnBackground => (theme.placeholder, theme.background),
+            Self::MenuText => (theme.menu().text, theme.menu().background),
+            Self::TooltipText => (theme.tooltip().text, theme.tooltip().background),
+        };
+        let background = theme.background.blend(background);
+        (background.blend(text), background)
+    }
+
+    /// Sets the text color of the pair. Component colors are overridden for
+    /// the component alone, leaving the shared text color alone.
+    fn set_text(self, theme: &mut Theme, color: Hsla) {
+        match self {
+            Self::TextOnBackground | Self::TextOnSelection => theme.text = color,
+            Self::PlaceholderOnBackground => theme.placeholder = color,
+            Self::MenuText => theme.components.menu.text = Some(color),
+            Self::TooltipText => theme.components.tooltip.text = Some(color),
+        }
+    }
+}
+
+/// A pair below the required contrast.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContrastIssue {
+    pub pair: ContrastPair,
+    pub text: Hsla,
+    pub background: Hsla,
+    pub ratio: f32,
+    pub required: f32,
+    /// The closest text color with enough contrast, only changing its
+    /// lightness if possible.
+    pub suggested: Hsla,
+}
+
+impl Theme {
+    /// Checks the pairs in [`ContrastPair::ALL`] against WCAG AA.
+    pub fn audit(&self) -> Vec<ContrastIssue> {
+        ContrastPair::ALL
+            .into_iter()
+            .filter_map(|pair| {
+                let (text, background) = pair.colors(self);
+                let ratio = contrast_ratio(text, background);
+                (ratio < AA_CONTRAST).then(|| ContrastIssue {
+                    pair,
+                    text,
+                    background,
+                    ratio,
+                    required: AA_CONTRAST,
+                    suggested: suggest(text, background, AA_CONTRAST),
+                })
+            })
+            .collect()
+    }
+
+    /// Applies the suggested colors until the theme passes [`Theme::audit`].
+    /// Returns the issues left, if fixing a pair keeps breaking another.
+    pub fn fix_contrast(&mut self) -> Vec<ContrastIssue> {
+        for _ in 0..MAX_FIX_ROUNDS {
+            let issues = self.audit();
+            if issues.is_empty() {
+                break;
+            }
+            for issue in issues {
+                issue.pair.set_text(self, issue.suggested);
+            }
+        }
+        self.audit()
+    }
+}
+
+/// Relative luminance of an opaque color, as defined by WCAG.
+pub fn luminance(color: Hsla) -> f32 {
+    let rgba = color.to_rgb();
+    let channel = |c: f32| {
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * channel(rgba.r) + 0.7152 * channel(rgba.g) + 0.0722 * channel(rgba.b)
+}
+
+/// WCAG contrast ratio between two opaque colors, from 1 to 21.
+pub fn contrast_ratio(a: Hsla, b: Hsla) -> f32 {
+    let (a, b) = (luminance(a), luminance(b));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// Moves the lightness of `text` away from `background` until the contrast
+/// reaches `required`, falling back to black or white.
+pub fn suggest(text: Hsla, background: Hsla, required: f32) -> Hsla {
+    let step = 0.01;
+    let search = |direction: f32| {
+        let mut color = text;
+        while (0.0..=1.0).contains(&(color.l + direction * step)) {
+            color.l += direction * step;
+            if contrast_ratio(color, background) >= required {
+                return Some(color);
+            }
+        }
+        None
+    };
+    match (search(1.0), search(-1.0)) {
+        (Some(lighter), Some(darker)) => {
+            if lighter.l - text.l <= text.l - darker.l {
+                lighter
+            } else {
+                darker
+            }
+        }
+        (Some(color), None) | (None, Some(color)) => color,
+        (None, None) => {
+            let (white, black) = (hsla(0.0, 0.0, 1.0, 1.0), hsla(0.0, 0.0, 0.0, 1.0));
+            if contrast_ratio(white, background) >= contrast_ratio(black, background) {
+                white
+            } else {
+                black
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn black_on_white_is_21() {
+        let (black, white) = (hsla(0.0, 0.0, 0.0, 1.0), hsla(0.0, 0.0, 1.0, 1.0));
+        assert!((contrast_ratio(black, white) - 21.0).abs() < 0.01);
+        assert_eq!(contrast_ratio(black, white), contrast_ratio(white, black));
+        assert!((contrast_ratio(white, white) - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn suggestions_reach_aa() {
+        let pairs = [
+            (rgb(0x403d52), rgb(0x191724)),
+            (rgb(0xe0def4), rgb(0x908caa)),
+            (rgb(0x777777), rgb(0x808080)),
+        ];
+        for (text, background) in pairs {
+            let suggested = suggest(text.into(), background.into(), AA_CONTRAST);
+            assert!(contrast_ratio(suggested, background.into()) >= AA_CONTRAST);
+        }
+    }
+
+    #[test]
+    fn fixes_default_theme() {
+        let mut theme = Theme::default();
+        assert!(!theme.audit().is_empty());
+        assert!(theme.fix_contrast().is_empty());
+        assert!(theme.audit().is_empty());
+    }
+}
@@ -0,0 +1,149 @@
+//! Colors in theme files, written as `#rrggbb`, `#rrggbbaa` or
+//! `hsl(210, 50%, 40%)` / `hsla(210, 50%, 40%, 0.5)`. The `{h, s, l, a}`
+//! objects of older theme files are still read.
+
+use gpui::*;
+use serde::{Deserialize, Deserializer, Serializer, de};
+
+/// Parses a hex or `hsl()` color.
+pub fn parse(value: &str) -> Result<Hsla, String> {
+    let value = value.trim();
+    if value.starts_with('#') {
+        return Rgba::try_from(value)
+            .map(Into::into)
+            .map_err(|_| format!("invalid hex color `{value}`"));
+    }
+    let args = value
+        .strip_prefix("hsla(")
+        .or_else(|| value.strip_prefix("hsl("))
+        .and_then(|rest| rest.strip_suffix(')'))
+        .ok_or_else(|| format!("expected `#rrggbb[aa]` or `hsl(..)`, found `{value}`"))?;
+    let parts = args
+        .split([',', '/', ' '])
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>();
+    let invalid = || format!("invalid hsl color `{value}`");
+    let number = |part: &str| part.trim().parse::<f32>().map_err(|_| invalid());
+    let fraction = |part: &str| match part.strip_suffix('%') {
+        Some(percent) => number(percent).map(|n| n / 100.0),
+        None => number(part),
+    };
+    let (h, s, l, a) = match parts.as_slice() {
+        [h, s, l] => (*h, *s, *l, "1"),
+        [h, s, l, a] => (*h, *s, *l, *a),
+        _ => return Err(invalid()),
+    };
+    let hue = number(h.strip_suffix("deg").unwrap_or(h))?.rem_euclid(360.0) / 360.0;
+    Ok(hsla(
+        hue,
+        fraction(s)?.clamp(0.0, 1.0),
+        fraction(l)?.clamp(0.0, 1.0),
+        fraction(a)?.clamp(0.0, 1.0),
+    ))
+}
+
+/// Formats as `#rrggbb`, or `#rrggbbaa` if not opaque.
+pub fn to_hex(color: Hsla) -> String {
+    let rgba = color.to_rgb();
+    let channel = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+    let hex = format!(
+        "#{:02x}{:02x}{:02x}",
+        channel(rgba.r),
+        channel(rgba.g),
+        channel(rgba.b)
+    );
+    if channel(rgba.a) == 255 {
+        hex
+    } else {
+        format!("{hex}{:02x}", channel(rgba.a))
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Repr {
+    Str(String),
+    Hsla(Hsla),
+}
+
+impl Repr {
+    fn into_color<E: de::Error>(self) -> Result<Hsla, E> {
+        match self {
+            Repr::Str(value) => parse(&value).map_err(E::custom),
+            Repr::Hsla(color) => Ok(color),
+        }
+    }
+}
+
+pub fn serialize<S: Serializer>(color: &Hsla, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&to_hex(*color))
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Hsla, D::Error> {
+    Repr::deserialize(deserializer)?.into_color()
+}
+
+/// For optional colors, written as `null` when unset.
+pub mod option {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        color: &Option<Hsla>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match color {
+            Some(color) => serializer.serialize_str(&to_hex(*color)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Hsla>, D::Error> {
+        Option::<Repr>::deserialize(deserializer)?
+            .map(Repr::into_color)
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn close(a: f32, b: f32) -> bool {
+        (a - b).abs() < 0.01
+    }
+
+    #[test]
+    fn parses_hex() {
+        let red = parse("#ff0000").unwrap().to_rgb();
+        assert!(close(red.r, 1.0) && close(red.g, 0.0) && close(red.a, 1.0));
+        assert!(close(parse("#ff000080").unwrap().a, 0.5));
+        assert!(parse("#12").is_err());
+    }
+
+    #[test]
+    fn parses_hsl() {
+        let color = parse("hsl(120, 50%, 40%)").unwrap();
+        assert!(close(color.h, 1.0 / 3.0) && close(color.s, 0.5) && close(color.l, 0.4));
+        assert!(close(color.a, 1.0));
+        assert!(close(parse("hsla(210, 50%, 40%, 0.5)").unwrap().a, 0.5));
+        assert!(close(parse("hsl(210deg 50% 40% / 50%)").unwrap().a, 0.5));
+        assert!(parse("hsl(210, 50%)").is_err());
+        assert!(parse("rgb(1, 2, 3)").is_err());
+    }
+
+    #[test]
+    fn round_trips_hex() {
+        for hex in ["#191724", "#e0def4", "#eb6f9280"] {
+            assert_eq!(to_hex(parse(hex).unwrap()), hex);
+        }
+    }
+
+    #[test]
+    fn deserializes_strings() {
+        let color = deserialize(serde_json::json!("hsl(0, 0%, 100%)")).unwrap();
+        assert_eq!(to_hex(color), "#ffffff");
+        assert!(deserialize(serde_json::json!("white")).is_err());
+    }
+}
@@ -0,0 +1,220 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde_json::{Map, Value};
+
+use super::{Error, Result, Theme, registry};
+
+pub(super) fn load(path: &Path) -> Result<Theme> {
+    let json = fs::read_to_string(path)?;
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let mut visited = vec![path.canonicalize()?];
+    parse_extending(&json, dir, &mut visited)
+}
+
+/// Parses a theme file whose `extends` paths are relative to `dir`.
+pub(super) fn parse(json: &str, dir: &Path) -> Result<Theme> {
+    parse_extending(json, dir, &mut Vec::new())
+}
+
+/// `visited` holds the canonical paths of the files extended so far.
+fn parse_extending(json: &str, dir: &Path, visited: &mut Vec<PathBuf>) -> Result<Theme> {
+    let overrides = serde_json::from_str(json)?;
+    let base = resolve_base(&overrides, dir, visited)?;
+    from_overrides(base, overrides)
+}
+
+/// The theme `overrides` get applied to, as JSON.
+fn resolve_base(overrides: &Value, dir: &Path, visited: &mut Vec<PathBuf>) -> Result<Value> {
+    let Some(name) = overrides.get("extends") else {
+        return Ok(serde_json::to_value(Theme::default())?);
+    };
+    let Some(name) = name.as_str() else {
+        return Err(Error::Key {
+            key: "extends".to_string(),
+            source: serde::de::Error::custom("expected the name or path of a theme"),
+        });
+    };
+
+    if let Some(path) = base_path(name, dir) {
+        let canonical = path.canonicalize()?;
+        if visited.contains(&canonical) {
+            return Err(Error::ExtendsCycle(name.to_string()));
+        }
+        visited.push(canonical);
+        let base: Value = serde_json::from_str(&fs::read_to_string(&path)?)?;
+        let dir = path.parent().unwrap_or(Path::new("."));
+        let mut resolved = resolve_base(&base, dir, visited)?;
+        merge(&mut resolved, without_extends(base));
+        return Ok(resolved);
+    }
+    match name {
+        registry::DEFAULT_DARK => Ok(serde_json::to_value(Theme::default())?),
+        registry::DEFAULT_LIGHT => Ok(serde_json::to_value(Theme::light())?),
+        _ => Err(Error::UnknownBase(name.to_string())),
+    }
+}
+
+/// A theme file called `name` in `dir`, with or without the extension.
+fn base_path(name: &str, dir: &Path) -> Option<PathBuf> {
+    [dir.join(name), dir.join(format!("{name}.json"))]
+        .into_iter()
+        .find(|path| path.is_file())
+}
+
+fn without_extends(mut value: Value) -> Value {
+    if let Value::Object(map) = &mut value {
+        map.remove("extends");
+    }
+    value
+}
+
+fn from_overrides(base: Value, overrides: Value) -> Result<Theme> {
+    let overrides = without_extends(overrides);
+    let mut merged = base.clone();
+    merge(&mut merged, overrides.clone());
+    serde_json::from_value(merged)
+        .map_err(|err| invalid_key(&base, &overrides, &mut Vec::new()).unwrap_or(Error::Json(err)))
+}
+
+/// Merges objects key by key, any other value replaces the one in `base`.
+fn merge(base: &mut Value, overrides: Value) {
+    match (base, overrides) {
+        (Value::Object(base), Value::Object(overrides)) => {
+            for (key, value) in overrides {
+                match base.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overrides) => *base = overrides,
+    }
+}
+
+/// Finds the key of `overrides` that fails to parse by applying them one at
+/// a time, descending into nested objects for the innermost key.
+fn invalid_key(base: &Value, overrides: &Value, path: &mut Vec<String>) -> Option<Error> {
+    let Value::Object(map) = overrides else {
+        return None;
+    };
+    for (key, value) in map {
+        path.push(key.clone());
+        let mut single = value.clone();
+        for key in path.iter().rev() {
+            single = Value::Object(Map::from_iter([(key.clone(), single)]));
+        }
+        let mut candidate = base.clone();
+        merge(&mut candidate, single);
+        if let Err(source) = serde_json::from_value::<Theme>(candidate) {
+            return invalid_key(base, value, path).or_else(|| {
+                Some(Error::Key {
+                    key: path.join("."),
+                    source,
+                })
+            });
+        }
+        path.pop();
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::theme::color::to_hex;
+
+    /// An empty directory for theme files, unique to the test.
+    fn theme_dir(test: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("gpui-yoso-{test}-{}", std::process::id()));
+        _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn extends_bundled_theme() {
+        let theme = parse(
+            r##"{"extends": "Rosé Pine Dawn", "text": "#000000"}"##,
+            Path::new("."),
+        )
+        .unwrap();
+        assert_eq!(to_hex(theme.background), to_hex(Theme::light().background));
+        assert_eq!(to_hex(theme.text), "#000000");
+    }
+
+    #[test]
+    fn extends_chains_of_files() {
+        let dir = theme_dir("extends-chain");
+        fs::write(
+            dir.join("base.json"),
+            r##"{"extends": "Rosé Pine Dawn", "error": "#ff0000"}"##,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("mid.json"),
+            r##"{"extends": "base", "warning": "#00ff00"}"##,
+        )
+        .unwrap();
+
+        let theme = parse(r##"{"extends": "mid.json", "hint": "#0000ff"}"##, &dir).unwrap();
+        assert_eq!(to_hex(theme.background), to_hex(Theme::light().background));
+        assert_eq!(to_hex(theme.error), "#ff0000");
+        assert_eq!(to_hex(theme.warning), "#00ff00");
+        assert_eq!(to_hex(theme.hint), "#0000ff");
+        _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn rejects_extends_cycles_and_unknown_bases() {
+        let dir = theme_dir("extends-cycle");
+        fs::write(dir.join("a.json"), r#"{"extends": "b"}"#).unwrap();
+        fs::write(dir.join("b.json"), r#"{"extends": "a"}"#).unwrap();
+
+        assert!(matches!(
+            parse(r#"{"extends": "a"}"#, &dir),
+            Err(Error::ExtendsCycle(_))
+        ));
+        assert!(matches!(
+            parse(r#"{"extends": "missing"}"#, &dir),
+            Err(Error::UnknownBase(name)) if name == "missing"
+        ));
+        _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn follows_long_chains_and_detects_self_extends() {
+        let dir = theme_dir("extends-long");
+        fs::write(dir.join("0.json"), r##"{"text": "#000000"}"##).unwrap();
+        for ix in 1..32 {
+            let json = format!(r#"{{"extends": "{}"}}"#, ix - 1);
+            fs::write(dir.join(format!("{ix}.json")), json).unwrap();
+        }
+        let theme = load(&dir.join("31.json")).unwrap();
+        assert_eq!(to_hex(theme.text), "#000000");
+
+        fs::write(dir.join("self.json"), r#"{"extends": "self"}"#).unwrap();
+        assert!(matches!(
+            load(&dir.join("self.json")),
+            Err(Error::ExtendsCycle(_))
+        ));
+        _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn names_the_invalid_key() {
+        let key = |json: &str| match parse(json, Path::new(".")) {
+            Err(Error::Key { key, .. }) => key,
+            other => panic!("expected an invalid key, got {other:?}"),
+        };
+        assert_eq!(key(r##"{"text": "#12"}"##), "text");
+        assert_eq!(
+            key(r#"{"components": {"button": {"hover": "nope"}}}"#),
+            "components.button.hover"
+        );
+    }
+}
@@ -0,0 +1,417 @@
+//! Converts color schemes of other editors into a [`Theme`], which can then
+//! be written as a theme file with [`Theme::save`]:
+//!
+//! ```ignore
+//! let (name, theme) = Theme::from_vscode_file("rose-pine-color-theme.json")?;
+//! theme.save(format!("themes/{name}.json"))?;
+//! ```
+//!
+//! Tokens without a counterpart in the scheme keep the value of the default
+//! dark or light theme, whichever matches the scheme.
+
+use std::{collections::HashMap, fs, path::Path};
+
+use gpui::Hsla;
+use serde::Deserialize;
+
+use super::{Error, Result, Theme, color};
+
+impl Theme {
+    /// Converts a VS Code color theme. Each token takes the first of these
+    /// workbench colors the theme defines:
+    ///
+    /// | token              | VS Code colors                                                       |
+    /// |--------------------|----------------------------------------------------------------------|
+    /// | `background`       | `editor.background`                                                  |
+    /// | `foreground`       | `sideBar.background`, `panel.background`, `editorGroupHeader.tabsBackground` |
+    /// | `muted_background` | `input.background`, `dropdown.background`, `editorWidget.background` |
+    /// | `muted`            | `disabledForeground`, `editorLineNumber.foreground`                  |
+    /// | `secondary`        | `descriptionForeground`, `tab.inactiveForeground`                    |
+    /// | `text`             | `editor.foreground`, `foreground`                                    |
+    /// | `error`            | `errorForeground`, `editorError.foreground`                          |
+    /// | `warning`          | `editorWarning.foreground`                                           |
+    /// | `hint`             | `editorInfo.foreground`, `textLink.foreground`                       |
+    /// | `selection`        | `editor.selectionBackground`, `list.activeSelectionBackground`       |
+    /// | `border`           | `panel.border`, `editorGroup.border`, `widget.border`, `contrastBorder` |
+    /// | `placeholder`      | `input.placeholderForeground`                                        |
+    /// | `accent`           | `focusBorder`, `button.background`, `activityBarBadge.background`    |
+    ///
+    /// The `type` of the theme picks the defaults for the rest. Returns the
+    /// name of the theme along with it.
+    pub fn from_vscode(json: &str) -> Result<(String, Self)> {
+        #[derive(Deserialize)]
+        struct VsCodeTheme {
+            #[serde(default)]
+            name: String,
+            #[serde(default, rename = "type")]
+            kind: Option<String>,
+            #[serde(default)]
+            colors: HashMap<String, String>,
+        }
+
+        let vscode: VsCodeTheme = serde_json::from_str(&strip_jsonc(json))?;
+        let pick = |keys: &[&str]| -> Result<Option<Hsla>> {
+            let Some((key, value)) = keys
+                .iter()
+                .find_map(|key| Some((key, vscode.colors.get(*key)?)))
+            else {
+                return Ok(None);
+            };
+            color::parse(value)
+                .map(Some)
+                .map_err(|err| Error::Scheme(format!("`{key}`: {err}")))
+        };
+
+        let mut theme = match vscode.kind.as_deref() {
+            Some("light") | Some("hcLight") => Theme::light(),
+            _ => Theme::default(),
+        };
+        let tokens: [(&mut Hsla, &[&str]); 13] = [
+            (&mut theme.background, &["editor.background"]),
+            (
+                &mut theme.foreground,
+                &[
+                    "sideBar.background",
+                    "panel.background",
+                    "editorGroupHeader.tabsBackground",
+                ],
+            ),
+            (
+                &mut theme.muted_background,
+                &[
+                    "input.background",
+                    "dropdown.background",
+                    "editorWidget.background",
+                ],
+            ),
+            (
+                &mut theme.muted,
+                &["disabledForeground", "editorLineNumber.foreground"],
+            ),
+            (
+                &mut theme.secondary,
+                &["descriptionForeground", "tab.inactiveForeground"],
+            ),
+            (&mut theme.text, &["editor.foreground", "foreground"]),
+            (
+                &mut theme.error,
+                &["errorForeground", "editorError.foreground"],
+            ),
+            (&mut theme.warning, &["editorWarning.foreground"]),
+            (
+                &mut theme.hint,
+                &["editorInfo.foreground", "textLink.foreground"],
+            ),
+            (
+                &mut theme.selection,
+                &[
+                    "editor.selectionBackground",
+                    "list.activeSelectionBackground",
+                ],
+            ),
+            (
+                &mut theme.border,
+                &[
+                    "panel.border",
+                    "editorGroup.border",
+                    "widget.border",
+                    "contrastBorder",
+                ],
+            ),
+            (&mut theme.placeholder, &["input.placeholderForeground"]),
+            (
+                &mut theme.accent,
+                &[
+                    "focusBorder",
+                    "button.background",
+                    "activityBarBadge.background",
+                ],
+            ),
+        ];
+        for (token, keys) in tokens {
+            if let Some(color) = pick(keys)? {
+                *token = color;
+            }
+        }
+        Ok((vscode.name, theme))
+    }
+
+    /// [`Theme::from_vscode`] for a file, named after the file if the theme
+    /// has no name.
+    pub fn from_vscode_file(path: impl AsRef<Path>) -> Result<(String, Self)> {
+        let (name, theme) = Self::from_vscode(&fs::read_to_string(path.as_ref())?)?;
+        Ok((name_or_stem(name, path.as_ref()), theme))
+    }
+
+    /// Converts a base16 or base24 scheme, in either the original format
+    /// with a top level `scheme` and bare hex values, or the newer one with
+    /// a `palette`. The base16 slots map to:
+    ///
+    /// | token              | slot     | meaning in base16              |
+    /// |--------------------|----------|--------------------------------|
+    /// | `background`       | `base00` | default background             |
+    /// | `foreground`       | `base01` | lighter background, bars       |
+    /// | `muted_background` | `base02` | selection background           |
+    /// | `selection`        | `base02` | selection background           |
+    /// | `border`           | `base02` | selection background           |
+    /// | `muted`            | `base03` | comments, invisibles           |
+    /// | `placeholder`      | `base03` | comments, invisibles           |
+    /// | `secondary`        | `base04` | dark foreground, bars          |
+    /// | `text`             | `base05` | default foreground             |
+    /// | `error`            | `base08` | red                            |
+    /// | `warning`          | `base0A` | yellow                         |
+    /// | `accent`           | `base0D` | blue                           |
+    /// | `hint`             | `base0E` | purple                         |
+    ///
+    /// The additional base24 slots have no counterpart and are ignored.
+    /// Returns the name of the scheme along with it.
+    pub fn from_base16(yaml: &str) -> Result<(String, Self)> {
+        let values = parse_flat_yaml(yaml);
+        let slot = |key: &str| -> Result<Hsla> {
+            let value = values
+                .get(key)
+                .or_else(|| values.get(&key.to_lowercase()))
+                .ok_or_else(|| Error::Scheme(format!("missing `{key}`")))?;
+            let hex = if value.starts_with('#') {
+                value.clone()
+            } else {
+                format!("#{value}")
+            };
+            color::parse(&hex).map_err(|err| Error::Scheme(format!("`{key}`: {err}")))
+        };
+
+        let background = slot("base00")?;
+        let base = if background.l < 0.5 {
+            Theme::default()
+        } else {
+            Theme::light()
+        };
+        let theme = Theme {
+            background,
+            foreground: slot("base01")?,
+            muted_background: slot("base02")?,
+            selection: slot("base02")?,
+            border: slot("base02")?,
+            muted: slot("base03")?,
+            placeholder: slot("base03")?,
+            secondary: slot("base04")?,
+            text: slot("base05")?,
+            error: slot("base08")?,
+            warning: slot("base0A")?,
+            accent: slot("base0D")?,
+            hint: slot("base0E")?,
+            ..base
+        };
+        let name = values
+            .get("name")
+            .or_else(|| values.get("scheme"))
+            .cloned()
+            .unwrap_or_default();
+        Ok((name, theme))
+    }
+
+    /// [`Theme::from_base16`] for a file, named after the file if the scheme
+    /// has no name.
+    pub fn from_base16_file(path: impl AsRef<Path>) -> Result<(String, Self)> {
+        let (name, theme) = Self::from_base16(&fs::read_to_string(path.as_ref())?)?;
+        Ok((name_or_stem(name, path.as_ref()), theme))
+    }
+}
+
+fn name_or_stem(name: String, path: &Path) -> String {
+    if !name.is_empty() {
+        return name;
+    }
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Reads the `key: value` lines of a base16 scheme, ignoring nesting, which
+/// is all the structure schemes have.
+fn parse_flat_yaml(yaml: &str) -> HashMap<String, String> {
+    yaml.lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            if line.starts_with('#') {
+                return None;
+            }
+            let (key, value) = line.split_once(':')?;
+            let value = value.trim();
+            // Comments after values, but not the `#` of quoted hex colors.
+            let value = match value.chars().next() {
+                Some(quote @ ('"' | '\'')) => value[1..].split(quote).next()?,
+                _ => value.split(" #").next()?.trim(),
+            };
+            Some((key.trim().to_string(), value.to_string()))
+        })
+        .filter(|(_, value)| !value.is_empty())
+        .collect()
+}
+
+/// Drops the comments and trailing commas VS Code allows in theme files.
+/// Comments go first, as they may sit between a trailing comma and the end
+/// of the object.
+fn strip_jsonc(json: &str) -> String {
+    strip_trailing_commas(&strip_comments(json))
+}
+
+fn strip_comments(json: &str) -> String {
+    let mut out = String::with_capacity(json.len());
+    let mut chars = json.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                '\\' => out.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                out.push(c);
+            }
+            ('/', Some('/')) => while chars.next_if(|&c| c != '\n').is_some() {},
+            ('/', Some('*')) => {
+                chars.next();
+                let mut last = ' ';
+                for c in chars.by_ref() {
+                    if last == '*' && c == '/' {
+                        break;
+                    }
+                    last = c;
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn strip_trailing_commas(json: &str) -> String {
+    let mut out = String::with_capacity(json.len());
+    let mut chars = json.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                '\\' => out.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => {
+                in_string = true;
+                out.push(c);
+            }
+            ',' => {
+                let rest = chars.clone().find(|c| !c.is_whitespace());
+                if !matches!(rest, Some('}' | ']')) {
+                    out.push(c);
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::theme::color::to_hex;
+
+    const VSCODE_THEME: &str = r##"{
+        // comments and trailing commas as VS Code writes them
+        "name": "Fixture Light",
+        "type": "light",
+        "colors": {
+            "editor.background": "#ffffff",
+            "panel.background": "#f0f0f0",
+            "dropdown.background": "#e0e0e0",
+            "editor.foreground": "#111111",
+            "foreground": "#222222",
+            "editorError.foreground": "#ff0000",
+            "list.activeSelectionBackground": "#c0c0ff",
+            "input.placeholderForeground": "#777777",
+            "button.background": "#0000ff",
+        },
+    }"##;
+
+    const BASE16_SCHEME: &str = r#"
+scheme: "Fixture Dark"
+author: "someone"
+base00: "101010" # background
+base01: "202020"
+base02: "303030"
+base03: "404040"
+base04: "505050"
+base05: "e0e0e0"
+base06: "f0f0f0"
+base07: "ffffff"
+base08: "ff0000"
+base09: "ff8000"
+base0A: "ffff00"
+base0B: "00ff00"
+base0C: "00ffff"
+base0D: "0000ff"
+base0E: "ff00ff"
+base0F: "800000"
+"#;
+
+    #[test]
+    fn maps_vscode_colors() {
+        let (name, theme) = Theme::from_vscode(VSCODE_THEME).unwrap();
+        assert_eq!(name, "Fixture Light");
+        assert_eq!(to_hex(theme.background), "#ffffff");
+        // the first key a theme defines wins
+        assert_eq!(to_hex(theme.foreground), "#f0f0f0");
+        assert_eq!(to_hex(theme.muted_background), "#e0e0e0");
+        assert_eq!(to_hex(theme.text), "#111111");
+        assert_eq!(to_hex(theme.error), "#ff0000");
+        assert_eq!(to_hex(theme.selection), "#c0c0ff");
+        assert_eq!(to_hex(theme.placeholder), "#777777");
+        assert_eq!(to_hex(theme.accent), "#0000ff");
+        // missing colors come from the light default
+        assert_eq!(to_hex(theme.warning), to_hex(Theme::light().warning));
+    }
+
+    #[test]
+    fn maps_base16_slots() {
+        let (name, theme) = Theme::from_base16(BASE16_SCHEME).unwrap();
+        assert_eq!(name, "Fixture Dark");
+        assert_eq!(to_hex(theme.background), "#101010");
+        assert_eq!(to_hex(theme.foreground), "#202020");
+        assert_eq!(to_hex(theme.selection), "#303030");
+        assert_eq!(to_hex(theme.placeholder), "#404040");
+        assert_eq!(to_hex(theme.secondary), "#505050");
+        assert_eq!(to_hex(theme.text), "#e0e0e0");
+        assert_eq!(to_hex(theme.error), "#ff0000");
+        assert_eq!(to_hex(theme.warning), "#ffff00");
+        assert_eq!(to_hex(theme.accent), "#0000ff");
+        assert_eq!(to_hex(theme.hint), "#ff00ff");
+        assert!(Theme::from_base16("scheme: \"Incomplete\"\nbase00: \"101010\"").is_err());
+    }
+
+    #[test]
+    fn strips_comments_and_trailing_commas() {
+        let json = r##"{
+            // a comment
+            "colors": {
+                "editor.background": "#191724", /* inline */
+                "editor.foreground": "#e0def4", // after the last entry
+            },
+            "name": "a // not a comment",
+        }"##;
+        let value: serde_json::Value = serde_json::from_str(&strip_jsonc(json)).unwrap();
+        assert_eq!(value["colors"]["editor.foreground"], "#e0def4");
+        assert_eq!(value["name"], "a // not a comment");
+    }
+}
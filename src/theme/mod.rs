@@ -0,0 +1,435 @@
+pub mod audit;
+pub mod color;
+mod format;
+pub mod import;
+pub mod registry;
+pub mod scope;
+pub mod tokens;
+
+use crate::animation::{self, Easing, Lerp, THEME_FADE_DURATION, Transition};
+use gpui::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    borrow::Borrow,
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+use tokens::*;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("failed to parse theme: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("invalid theme key `{key}`: {source}")]
+    Key {
+        key: String,
+        source: serde_json::Error,
+    },
+    #[error("unknown base theme `{0}`")]
+    UnknownBase(String),
+    #[error("theme extends itself through `{0}`")]
+    ExtendsCycle(String),
+    #[error("invalid color scheme: {0}")]
+    Scheme(String),
+    #[error("io: {0}")]
+    Io(#[from] io::Error),
+}
+
+type Result<T> = std::result::Result<T, Error>;
+
+/// How often a watched theme file is checked for changes.
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Interval between the frames of the cross-fade between themes.
+const FADE_FRAME_INTERVAL: Duration = Duration::from_millis(16);
+
+/// Colors and design tokens of the UI.
+///
+/// Theme files only need the keys they change. Missing ones are taken from
+/// the default theme, or from the theme named by an `extends` key: one of
+/// the bundled themes, or a file next to the extending one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    #[serde(with = "color")]
+    pub background: Hsla,
+    #[serde(with = "color")]
+    pub foreground: Hsla,
+    #[serde(with = "color")]
+    pub muted_background: Hsla,
+    #[serde(with = "color")]
+    pub muted: Hsla,
+    #[serde(with = "color")]
+    pub secondary: Hsla,
+    #[serde(with = "color")]
+    pub text: Hsla,
+    #[serde(with = "color")]
+    pub error: Hsla,
+    #[serde(with = "color")]
+    pub warning: Hsla,
+    #[serde(with = "color")]
+    pub hint: Hsla,
+    #[serde(with = "color")]
+    pub selection: Hsla,
+    #[serde(with = "color")]
+    pub border: Hsla,
+    /// Text shown in empty inputs.
+    #[serde(with = "color")]
+    pub placeholder: Hsla,
+    /// Highlights the focused window, e.g. along its resize edge.
+    #[serde(with = "color")]
+    pub accent: Hsla,
+    /// Dims the window behind modals.
+    #[serde(with = "color")]
+    pub backdrop: Hsla,
+    pub typography: Typography,
+    pub spacing: Spacing,
+    pub radii: Radii,
+    pub sizes: Sizes,
+    pub shadows: Shadows,
+    pub components: Components,
+}
+
+impl Global for Theme {}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            background: rgb(0x191724).into(),
+            foreground: rgb(0x1f1d2e).into(),
+            muted_background: rgb(0x26233a).into(),
+            muted: rgb(0x6e6a86).into(),
+            secondary: rgb(0x908caa).into(),
+            text: rgb(0xe0def4).into(),
+            error: rgb(0xeb6f92).into(),
+            warning: rgb(0xf6c177).into(),
+            hint: rgb(0xc4a7e7).into(),
+            selection: rgb(0x403d52).into(),
+            border: rgb(0x524f67).into(),
+            placeholder: rgb(0x403d52).into(),
+            accent: rgb(0xfb94bc).into(),
+            backdrop: hsla(0.0, 0.0, 0.0, 0.45),
+            typography: Typography::default(),
+            spacing: Spacing::default(),
+            radii: Radii::default(),
+            sizes: Sizes::default(),
+            shadows: Shadows::default(),
+            components: Components::default(),
+        }
+    }
+}
+
+impl Theme {
+    /// Rosé Pine Dawn, the light counterpart of the default theme.
+    pub fn light() -> Self {
+        Self {
+            background: rgb(0xfaf4ed).into(),
+            foreground: rgb(0xfffaf3).into(),
+            muted_background: rgb(0xf2e9e1).into(),
+            muted: rgb(0x9893a5).into(),
+            secondary: rgb(0x797593).into(),
+            text: rgb(0x575279).into(),
+            error: rgb(0xb4637a).into(),
+            warning: rgb(0xea9d34).into(),
+            hint: rgb(0x907aa9).into(),
+            selection: rgb(0xdfdad9).into(),
+            border: rgb(0xcecacd).into(),
+            placeholder: rgb(0xdfdad9).into(),
+            accent: rgb(0xd7827e).into(),
+            ..Self::default()
+        }
+    }
+
+    /// Whether the theme has a dark background.
+    pub fn is_dark(&self) -> bool {
+        self.background.l < 0.5
+    }
+
+    pub fn button(&self) -> ComponentColors {
+        self.components.button.resolve(ComponentColors {
+            background: transparent_black(),
+            hover: self.foreground,
+            active: self.selection,
+            text: self.text,
+            border: self.border,
+            radius: self.radii.lg,
+        })
+    }
+
+    pub fn tab(&self) -> ComponentColors {
+        self.components.tab.resolve(ComponentColors {
+            background: self.background,
+            hover: self.selection,
+            active: self.selection,
+            text: self.text,
+            border: self.border,
+            radius: px(0.0),
+        })
+    }
+
+    pub fn menu(&self) -> ComponentColors {
+        self.components.menu.resolve(ComponentColors {
+            background: self.foreground,
+            hover: self.selection,
+            active: self.selection,
+            text: self.text,
+            border: self.border,
+            radius: self.radii.lg,
+        })
+    }
+
+    /// The active color is the one of selected text.
+    pub fn input(&self) -> ComponentColors {
+        self.components.input.resolve(ComponentColors {
+            background: transparent_black(),
+            hover: transparent_black(),
+            active: self.selection,
+            text: self.text,
+            border: self.border,
+            radius: self.radii.sm,
+        })
+    }
+
+    pub fn tooltip(&self) -> ComponentColors {
+        self.components.tooltip.resolve(ComponentColors {
+            background: self.secondary,
+            hover: self.secondary,
+            active: self.secondary,
+            text: self.text,
+            border: self.border,
+            radius: self.radii.lg,
+        })
+    }
+
+    /// Parses a theme, resolving `extends` against the bundled themes and
+    /// files relative to the working directory.
+    pub fn from_json(json: &str) -> Result<Self> {
+        format::parse(json, Path::new("."))
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> Result<(String, Self)> {
+        Ok((
+            path.as_ref().display().to_string(),
+            format::load(path.as_ref())?,
+        ))
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let str = serde_json::to_string_pretty(self)?;
+        fs::write(path, &str)?;
+        Ok(())
+    }
+}
+
+pub trait AppTheme {
+    fn theme(&self) -> &Theme;
+}
+
+impl<T: AppContext + Borrow<App>> AppTheme for T {
+    fn theme(&self) -> &Theme {
+        let cx: &App = (*self).borrow();
+        // Inside a `ThemeScope` while it is drawn.
+        cx.try_global::<scope::ScopedThemes>()
+            .and_then(|scoped| scoped.0.last())
+            .map(|theme| &**theme)
+            .unwrap_or_else(|| Theme::global(cx))
+    }
+}
+
+pub fn init(cx: &mut App) {
+    cx.set_global(Theme::default());
+}
+
+/// Blends the colors, the other tokens are taken from `to` right away.
+impl Lerp for Theme {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        Self {
+            background: self.background.lerp(&to.background, t),
+            foreground: self.foreground.lerp(&to.foreground, t),
+            muted_background: self.muted_background.lerp(&to.muted_background, t),
+            muted: self.muted.lerp(&to.muted, t),
+            secondary: self.secondary.lerp(&to.secondary, t),
+            text: self.text.lerp(&to.text, t),
+            error: self.error.lerp(&to.error, t),
+            warning: self.warning.lerp(&to.warning, t),
+            hint: self.hint.lerp(&to.hint, t),
+            selection: self.selection.lerp(&to.selection, t),
+            border: self.border.lerp(&to.border, t),
+            placeholder: self.placeholder.lerp(&to.placeholder, t),
+            accent: self.accent.lerp(&to.accent, t),
+            backdrop: self.backdrop.lerp(&to.backdrop, t),
+            components: self.components.lerp(&to.components, t),
+            ..to.clone()
+        }
+    }
+}
+
+/// The cross-fade started by the latest [`set_theme`], removed once it is
+/// done.
+struct ThemeFade {
+    from: Theme,
+    to: Theme,
+    progress: Transition<f32>,
+    _task: Task<()>,
+}
+
+impl Global for ThemeFade {}
+
+/// Makes `theme` the active theme right away, stopping a running fade. For
+/// loading the first theme, before there is anything to fade from.
+pub fn set_theme_immediate(cx: &mut App, theme: Theme) {
+    if !cx.has_global::<Theme>() {
+        init(cx);
+    }
+    if cx.has_global::<ThemeFade>() {
+        cx.remove_global::<ThemeFade>();
+    }
+    cx.update_global(|active_theme, _| {
+        *active_theme = theme;
+    });
+}
+
+/// Makes `theme` the active theme, cross-fading from the current one over
+/// [`THEME_FADE_DURATION`], so the [`Theme`] global only holds `theme` once
+/// the fade is done. Swaps right away like [`set_theme_immediate`] with
+/// reduced motion enabled or while there are no windows to show the fade.
+pub fn set_theme(cx: &mut App, theme: Theme) {
+    if !cx.has_global::<Theme>() || animation::reduced_motion(cx) || cx.windows().is_empty() {
+        set_theme_immediate(cx, theme);
+        return;
+    }
+
+    let mut progress = Transition::new(0.0)
+        .duration(THEME_FADE_DURATION)
+        .easing(Easing::EaseInOut);
+    progress.set_target(1.0, cx);
+    let task = cx.spawn(async move |cx| {
+        loop {
+            cx.background_executor().timer(FADE_FRAME_INTERVAL).await;
+            let done = cx.update(|cx| {
+                let Some(fade) = cx.try_global::<ThemeFade>() else {
+                    return true;
+                };
+                let done = !fade.progress.is_animating();
+                let theme = if done {
+                    fade.to.clone()
+                } else {
+                    fade.from.lerp(&fade.to, fade.progress.value())
+                };
+                cx.update_global(|active_theme: &mut Theme, _| *active_theme = theme);
+                if done {
+                    cx.remove_global::<ThemeFade>();
+                }
+                cx.refresh_windows();
+                done
+            });
+            if done.unwrap_or(true) {
+                break;
+            }
+        }
+    });
+    // Fading on from wherever a running fade got to.
+    let from = Theme::global(cx).clone();
+    cx.set_global(ThemeFade {
+        from,
+        to: theme,
+        progress,
+        _task: task,
+    });
+}
+
+/// Reloads a theme file and refreshes all windows whenever the file changes,
+/// for as long as the watcher is kept around.
+///
+/// A file that fails to load is reported to the error handler and through
+/// `tracing`, the last theme that loaded stays active.
+pub struct ThemeWatcher {
+    _task: Task<()>,
+}
+
+impl ThemeWatcher {
+    /// Loads the theme right away and then watches the file.
+    pub fn new(
+        path: impl Into<PathBuf>,
+        cx: &mut App,
+        on_error: impl Fn(&Error, &mut App) + 'static,
+    ) -> Self {
+        let path = path.into();
+        let task = cx.spawn(async move |cx| {
+            let mut last_modified: Option<SystemTime> = None;
+            loop {
+                // Off the UI thread, only applying the theme happens there.
+                let (modified, loaded) = cx
+                    .background_executor()
+                    .spawn({
+                        let path = path.clone();
+                        async move {
+                            let modified =
+                                fs::metadata(&path).and_then(|meta| meta.modified()).ok();
+                            let loaded = (modified.is_some() && modified != last_modified)
+                                .then(|| Theme::from_file(&path));
+                            (modified, loaded)
+                        }
+                    })
+                    .await;
+                if let Some(loaded) = loaded {
+                    last_modified = modified;
+                    let updated = cx.update(|cx| match loaded {
+                        Ok((_, theme)) => {
+                            set_theme(cx, theme);
+                            cx.refresh_windows();
+                        }
+                        Err(err) => {
+                            tracing::error!("failed to reload theme {}: {err}", path.display());
+                            on_error(&err, cx);
+                        }
+                    });
+                    if updated.is_err() {
+                        break;
+                    }
+                }
+                cx.background_executor().timer(WATCH_INTERVAL).await;
+            }
+        });
+        Self { _task: task }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::Cell, rc::Rc};
+
+    use super::*;
+
+    #[gpui::test]
+    async fn watcher_keeps_last_good_theme(cx: &mut TestAppContext) {
+        let dir = std::env::temp_dir().join(format!("gpui-yoso-watcher-{}", std::process::id()));
+        _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("theme.json");
+        fs::write(&path, r##"{"text": "#000000"}"##).unwrap();
+
+        let errors = Rc::new(Cell::new(0));
+        let _watcher = cx.update(|cx| {
+            let errors = errors.clone();
+            ThemeWatcher::new(&path, cx, move |_, _| errors.set(errors.get() + 1))
+        });
+        cx.run_until_parked();
+        let text = |cx: &mut TestAppContext| cx.read(|cx| color::to_hex(Theme::global(cx).text));
+        assert_eq!(text(cx), "#000000");
+
+        fs::write(&path, "{ broken").unwrap();
+        fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(10))
+            .unwrap();
+        cx.executor().advance_clock(WATCH_INTERVAL);
+        cx.run_until_parked();
+        assert_eq!(errors.get(), 1);
+        assert_eq!(text(cx), "#000000");
+        _ = fs::remove_dir_all(dir);
+    }
+}
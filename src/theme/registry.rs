@@ -0,0 +1,322 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use gpui::*;
+use serde::{Deserialize, Serialize};
+
+use super::{Error, Result, Theme, set_theme, set_theme_immediate};
+
+pub const DEFAULT_DARK: &str = "Rosé Pine";
+pub const DEFAULT_LIGHT: &str = "Rosé Pine Dawn";
+
+/// Which of the two selected themes is active.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ThemeMode {
+    Light,
+    Dark,
+    /// Follows the appearance of the OS.
+    #[default]
+    System,
+}
+
+/// The user's choice of themes, saved by the [`ThemeRegistry`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemeSelection {
+    pub mode: ThemeMode,
+    /// Name of the theme used in light mode.
+    pub light: String,
+    /// Name of the theme used in dark mode.
+    pub dark: String,
+}
+
+impl Default for ThemeSelection {
+    fn default() -> Self {
+        Self {
+            mode: ThemeMode::System,
+            light: DEFAULT_LIGHT.to_string(),
+            dark: DEFAULT_DARK.to_string(),
+        }
+    }
+}
+
+/// All themes known to the app, and which light and dark one is selected.
+///
+/// Starts out with the bundled themes. Call [`ThemeRegistry::follow_system`]
+/// for a window to switch between the pair when the OS appearance changes.
+pub struct ThemeRegistry {
+    themes: BTreeMap<SharedString, Theme>,
+    selection: ThemeSelection,
+    system_dark: bool,
+    selection_path: Option<PathBuf>,
+}
+
+impl Global for ThemeRegistry {}
+
+impl Default for ThemeRegistry {
+    fn default() -> Self {
+        Self {
+            themes: BTreeMap::from([
+                (DEFAULT_DARK.into(), Theme::default()),
+                (DEFAULT_LIGHT.into(), Theme::light()),
+            ]),
+            selection: ThemeSelection::default(),
+            system_dark: true,
+            selection_path: None,
+        }
+    }
+}
+
+impl ThemeRegistry {
+    /// Registers every `.json` theme in `dir` under its file name. Themes
+    /// that fail to load are skipped and returned with their error.
+    pub fn load_dir(dir: impl AsRef<Path>, cx: &mut App) -> Result<Vec<(PathBuf, Error)>> {
+        let mut failed = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            let Some(name) = path
+                .file_stem()
+                .map(|name| name.to_string_lossy().to_string())
+            else {
+                continue;
+            };
+            match Theme::from_file(&path) {
+                Ok((_, theme)) => Self::register(name, theme, cx),
+                Err(err) => {
+                    tracing::error!("failed to load theme {}: {err}", path.display());
+                    failed.push((path, err));
+                }
+            }
+        }
+        Self::apply(false, cx);
+        Ok(failed)
+    }
+
+    /// Adds or replaces the theme called `name`.
+    pub fn register(name: impl Into<SharedString>, theme: Theme, cx: &mut App) {
+        cx.default_global::<Self>()
+            .themes
+            .insert(name.into(), theme);
+    }
+
+    pub fn names(cx: &mut App) -> Vec<SharedString> {
+        cx.default_global::<Self>().themes.keys().cloned().collect()
+    }
+
+    /// Names of the themes with a light or dark background, for picking
+    /// either half of the pair.
+    pub fn names_for(dark: bool, cx: &mut App) -> Vec<SharedString> {
+        cx.default_global::<Self>()
+            .themes
+            .iter()
+            .filter(|(_, theme)| theme.is_dark() == dark)
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    pub fn get(name: &str, cx: &mut App) -> Option<Theme> {
+        cx.default_global::<Self>().themes.get(name).cloned()
+    }
+
+    pub fn selection(cx: &mut App) -> ThemeSelection {
+        cx.default_global::<Self>().selection.clone()
+    }
+
+    /// Loads the selection saved at `path` and applies it. Later changes to
+    /// the selection get saved there. A missing file keeps the defaults.
+    pub fn load_selection(path: impl Into<PathBuf>, cx: &mut App) -> Result<()> {
+        let path = path.into();
+        let selection = match fs::read_to_string(&path) {
+            Ok(json) => Some(serde_json::from_str(&json)?),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+            Err(err) => return Err(err.into()),
+        };
+        let this = cx.default_global::<Self>();
+        this.selection_path = Some(path);
+        if let Some(selection) = selection {
+            this.selection = selection;
+        }
+        Self::apply(false, cx);
+        Ok(())
+    }
+
+    pub fn set_selection(selection: ThemeSelection, cx: &mut App) {
+        cx.default_global::<Self>().selection = selection;
+        Self::apply(true, cx);
+        Self::save_selection(cx);
+    }
+
+    pub fn set_mode(mode: ThemeMode, cx: &mut App) {
+        let selection = ThemeSelection {
+            mode,
+            ..Self::selection(cx)
+        };
+        Self::set_selection(selection, cx);
+    }
+
+    /// Selects the theme used in light mode.
+    pub fn set_light(name: impl Into<String>, cx: &mut App) {
+        let selection = ThemeSelection {
+            light: name.into(),
+            ..Self::selection(cx)
+        };
+        Self::set_selection(selection, cx);
+    }
+
+    /// Selects the theme used in dark mode.
+    pub fn set_dark(name: impl Into<String>, cx: &mut App) {
+        let selection = ThemeSelection {
+            dark: name.into(),
+            ..Self::selection(cx)
+        };
+        Self::set_selection(selection, cx);
+    }
+
+    /// Switches between the light and dark theme along with the appearance
+    /// of `window`, as long as the mode is [`ThemeMode::System`].
+    pub fn follow_system(window: &mut Window, cx: &mut App) {
+        Self::set_appearance(window.appearance(), false, cx);
+        window
+            .observe_window_appearance(|window, cx| {
+                Self::set_appearance(window.appearance(), true, cx)
+            })
+            .detach();
+    }
+
+    fn set_appearance(appearance: WindowAppearance, fade: bool, cx: &mut App) {
+        let dark = matches!(
+            appearance,
+            WindowAppearance::Dark | WindowAppearance::VibrantDark
+        );
+        let this = cx.default_global::<Self>();
+        if this.system_dark != dark {
+            this.system_dark = dark;
+            Self::apply(fade, cx);
+        }
+    }
+
+    /// Name of the theme the selection currently resolves to.
+    pub fn active_name(cx: &mut App) -> String {
+        let this = cx.default_global::<Self>();
+        let dark = match this.selection.mode {
+            ThemeMode::Light => false,
+            ThemeMode::Dark => true,
+            ThemeMode::System => this.system_dark,
+        };
+        if dark {
+            this.selection.dark.clone()
+        } else {
+            this.selection.light.clone()
+        }
+    }
+
+    /// Makes the selected theme the active one, fading to it when the user
+    /// switched themes. Unknown names keep the current theme.
+    fn apply(fade: bool, cx: &mut App) {
+        let name = Self::active_name(cx);
+        match Self::get(&name, cx) {
+            Some(theme) => {
+                if fade {
+                    set_theme(cx, theme);
+                } else {
+                    set_theme_immediate(cx, theme);
+                }
+                cx.refresh_windows();
+            }
+            None => tracing::warn!("selected theme {name} is not registered"),
+        }
+    }
+
+    fn save_selection(cx: &mut App) {
+        let this = cx.default_global::<Self>();
+        let Some(path) = this.selection_path.as_ref() else {
+            return;
+        };
+        let saved = serde_json::to_string_pretty(&this.selection)
+            .map_err(Error::from)
+            .and_then(|json| Ok(fs::write(path, json)?));
+        if let Err(err) = saved {
+            tracing::error!("failed to save theme selection: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::theme::color::to_hex;
+
+    fn theme_dir(test: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("gpui-yoso-{test}-{}", std::process::id()));
+        _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("good.json"), r#"{"extends": "Rosé Pine Dawn"}"#).unwrap();
+        fs::write(dir.join("broken.json"), "{ broken").unwrap();
+        fs::write(dir.join("notes.txt"), "not a theme").unwrap();
+        dir
+    }
+
+    #[gpui::test]
+    fn loads_dir_skipping_broken_themes(cx: &mut TestAppContext) {
+        let dir = theme_dir("registry-load");
+        cx.update(|cx| {
+            let failed = ThemeRegistry::load_dir(&dir, cx).unwrap();
+            assert_eq!(failed.len(), 1);
+            assert_eq!(failed[0].0, dir.join("broken.json"));
+            assert!(ThemeRegistry::names(cx).contains(&"good".into()));
+            assert!(ThemeRegistry::names_for(false, cx).contains(&"good".into()));
+            assert!(!ThemeRegistry::names(cx).contains(&"notes".into()));
+        });
+        _ = fs::remove_dir_all(dir);
+    }
+
+    #[gpui::test]
+    fn persists_selection(cx: &mut TestAppContext) {
+        let dir = theme_dir("registry-selection");
+        let path = dir.join("selection.json");
+        cx.update(|cx| {
+            ThemeRegistry::load_dir(&dir, cx).unwrap();
+            ThemeRegistry::load_selection(&path, cx).unwrap();
+            assert_eq!(ThemeRegistry::selection(cx), ThemeSelection::default());
+
+            ThemeRegistry::set_light("good", cx);
+            ThemeRegistry::set_mode(ThemeMode::Light, cx);
+            assert!(path.is_file());
+
+            cx.set_global(ThemeRegistry::default());
+            ThemeRegistry::load_dir(&dir, cx).unwrap();
+            ThemeRegistry::load_selection(&path, cx).unwrap();
+            let selection = ThemeRegistry::selection(cx);
+            assert_eq!(selection.mode, ThemeMode::Light);
+            assert_eq!(selection.light, "good");
+            assert_eq!(ThemeRegistry::active_name(cx), "good");
+            assert_eq!(
+                to_hex(Theme::global(cx).background),
+                to_hex(Theme::light().background)
+            );
+        });
+        _ = fs::remove_dir_all(dir);
+    }
+
+    #[gpui::test]
+    fn resolves_mode_against_system_appearance(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            ThemeRegistry::set_appearance(WindowAppearance::Light, false, cx);
+            assert_eq!(ThemeRegistry::active_name(cx), DEFAULT_LIGHT);
+            ThemeRegistry::set_appearance(WindowAppearance::VibrantDark, false, cx);
+            assert_eq!(ThemeRegistry::active_name(cx), DEFAULT_DARK);
+
+            ThemeRegistry::set_mode(ThemeMode::Light, cx);
+            assert_eq!(ThemeRegistry::active_name(cx), DEFAULT_LIGHT);
+            ThemeRegistry::set_mode(ThemeMode::Dark, cx);
+            ThemeRegistry::set_appearance(WindowAppearance::Light, false, cx);
+            assert_eq!(ThemeRegistry::active_name(cx), DEFAULT_DARK);
+        });
+    }
+}
@@ -0,0 +1,101 @@
+use std::rc::Rc;
+
+use gpui::*;
+
+use super::Theme;
+
+/// Themes of the [`ThemeScope`]s currently being drawn, innermost last.
+#[derive(Default)]
+pub(super) struct ScopedThemes(pub(super) Vec<Rc<Theme>>);
+
+impl Global for ScopedThemes {}
+
+/// Draws `child` with its own theme, for a part of the window that needs
+/// different colors than the rest of the app.
+///
+/// Components read the theme while they render and paint, so everything
+/// inside uses it. Listeners run later and see the app theme again, as do
+/// deferred elements like popups, which are drawn after the rest of the
+/// window.
+pub struct ThemeScope {
+    theme: Rc<Theme>,
+    child: AnyElement,
+}
+
+impl ThemeScope {
+    pub fn new(theme: impl Into<Rc<Theme>>, child: impl IntoElement) -> Self {
+        Self {
+            theme: theme.into(),
+            child: child.into_any_element(),
+        }
+    }
+
+    fn scoped<R>(&mut self, cx: &mut App, f: impl FnOnce(&mut AnyElement, &mut App) -> R) -> R {
+        cx.default_global::<ScopedThemes>()
+            .0
+            .push(self.theme.clone());
+        let result = f(&mut self.child, cx);
+        cx.default_global::<ScopedThemes>().0.pop();
+        result
+    }
+}
+
+impl IntoElement for ThemeScope {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+impl Element for ThemeScope {
+    type RequestLayoutState = ();
+
+    type PrepaintState = ();
+
+    fn id(&self) -> Option<ElementId> {
+        None
+    }
+
+    fn source_location(&self) -> Option<&'static std::panic::Location<'static>> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _: Option<&InspectorElementId>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        let layout_id = self.scoped(cx, |child, cx| child.request_layout(window, cx));
+        (layout_id, ())
+    }
+
+    fn prepaint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _: Option<&InspectorElementId>,
+        _: Bounds<Pixels>,
+        _: &mut Self::RequestLayoutState,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Self::PrepaintState {
+        self.scoped(cx, |child, cx| {
+            child.prepaint(window, cx);
+        });
+    }
+
+    fn paint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _: Option<&InspectorElementId>,
+        _: Bounds<Pixels>,
+        _: &mut Self::RequestLayoutState,
+        _: &mut Self::PrepaintState,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        self.scoped(cx, |child, cx| child.paint(window, cx));
+    }
+}
@@ -0,0 +1,232 @@
+use crate::animation::Lerp;
+use gpui::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Typography {
+    /// Font of the UI, the platform default if unset.
+    pub font_family: Option<SharedString>,
+    /// Font of code and key bindings, the platform default if unset.
+    pub mono_font_family: Option<SharedString>,
+    pub text_xs: Pixels,
+    pub text_sm: Pixels,
+    pub text_base: Pixels,
+    pub text_lg: Pixels,
+}
+
+impl Default for Typography {
+    fn default() -> Self {
+        Self {
+            font_family: None,
+            mono_font_family: None,
+            text_xs: px(12.0),
+            text_sm: px(14.0),
+            text_base: px(16.0),
+            text_lg: px(18.0),
+        }
+    }
+}
+
+impl Typography {
+    /// Sets the UI font on `element`, for the roots of popups and windows.
+    pub fn apply<E: Styled>(&self, mut element: E) -> E {
+        if let Some(family) = self.font_family.clone() {
+            element = element.font_family(family);
+        }
+        element.text_size(self.text_base)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Spacing {
+    pub xs: Pixels,
+    pub sm: Pixels,
+    pub md: Pixels,
+    pub lg: Pixels,
+    pub xl: Pixels,
+}
+
+impl Default for Spacing {
+    fn default() -> Self {
+        Self {
+            xs: px(2.0),
+            sm: px(4.0),
+            md: px(8.0),
+            lg: px(12.0),
+            xl: px(16.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Radii {
+    pub sm: Pixels,
+    pub md: Pixels,
+    pub lg: Pixels,
+}
+
+impl Default for Radii {
+    fn default() -> Self {
+        Self {
+            sm: px(2.0),
+            md: px(6.0),
+            lg: px(8.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Sizes {
+    /// Height of bars of controls, like tab bars and dock headers.
+    pub bar_height: Pixels,
+    pub title_bar_height: Pixels,
+    pub icon: Pixels,
+}
+
+impl Default for Sizes {
+    fn default() -> Self {
+        Self {
+            bar_height: px(36.0),
+            title_bar_height: px(32.0),
+            icon: px(16.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Shadow {
+    #[serde(with = "super::color")]
+    pub color: Hsla,
+    pub offset_x: Pixels,
+    pub offset_y: Pixels,
+    pub blur: Pixels,
+    pub spread: Pixels,
+}
+
+impl Shadow {
+    fn new(offset_y: f32, blur: f32, spread: f32) -> Self {
+        Self {
+            color: hsla(0.0, 0.0, 0.0, 0.1),
+            offset_x: px(0.0),
+            offset_y: px(offset_y),
+            blur: px(blur),
+            spread: px(spread),
+        }
+    }
+}
+
+impl From<Shadow> for BoxShadow {
+    fn from(shadow: Shadow) -> Self {
+        BoxShadow {
+            color: shadow.color,
+            offset: point(shadow.offset_x, shadow.offset_y),
+            blur_radius: shadow.blur,
+            spread_radius: shadow.spread,
+        }
+    }
+}
+
+/// Elevation levels, each made of layered shadows.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Shadows {
+    pub sm: Vec<Shadow>,
+    pub md: Vec<Shadow>,
+    pub lg: Vec<Shadow>,
+}
+
+impl Default for Shadows {
+    fn default() -> Self {
+        Self {
+            sm: vec![Shadow::new(1.0, 2.0, 0.0)],
+            md: vec![Shadow::new(4.0, 6.0, -1.0), Shadow::new(2.0, 4.0, -2.0)],
+            lg: vec![Shadow::new(10.0, 15.0, -3.0), Shadow::new(4.0, 6.0, -4.0)],
+        }
+    }
+}
+
+/// Converts a level of [`Shadows`] for [`Styled::shadow`].
+pub fn box_shadows(shadows: &[Shadow]) -> Vec<BoxShadow> {
+    shadows.iter().copied().map(Into::into).collect()
+}
+
+/// Overrides of the colors and rounding a component takes from the theme.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ComponentStyle {
+    #[serde(with = "super::color::option")]
+    pub background: Option<Hsla>,
+    #[serde(with = "super::color::option")]
+    pub hover: Option<Hsla>,
+    /// Selected or pressed.
+    #[serde(with = "super::color::option")]
+    pub active: Option<Hsla>,
+    #[serde(with = "super::color::option")]
+    pub text: Option<Hsla>,
+    #[serde(with = "super::color::option")]
+    pub border: Option<Hsla>,
+    pub radius: Option<Pixels>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Components {
+    pub button: ComponentStyle,
+    pub tab: ComponentStyle,
+    pub menu: ComponentStyle,
+    pub input: ComponentStyle,
+    pub tooltip: ComponentStyle,
+}
+
+/// A [`ComponentStyle`] with the theme defaults filled in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ComponentColors {
+    pub background: Hsla,
+    pub hover: Hsla,
+    pub active: Hsla,
+    pub text: Hsla,
+    pub border: Hsla,
+    pub radius: Pixels,
+}
+
+impl ComponentStyle {
+    pub(crate) fn resolve(&self, defaults: ComponentColors) -> ComponentColors {
+        ComponentColors {
+            background: self.background.unwrap_or(defaults.background),
+            hover: self.hover.unwrap_or(defaults.hover),
+            active: self.active.unwrap_or(defaults.active),
+            text: self.text.unwrap_or(defaults.text),
+            border: self.border.unwrap_or(defaults.border),
+            radius: self.radius.unwrap_or(defaults.radius),
+        }
+    }
+}
+
+impl Lerp for ComponentStyle {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        Self {
+            background: self.background.lerp(&to.background, t),
+            hover: self.hover.lerp(&to.hover, t),
+            active: self.active.lerp(&to.active, t),
+            text: self.text.lerp(&to.text, t),
+            border: self.border.lerp(&to.border, t),
+            radius: to.radius,
+        }
+    }
+}
+
+impl Lerp for Components {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        Self {
+            button: self.button.lerp(&to.button, t),
+            tab: self.tab.lerp(&to.tab, t),
+            menu: self.menu.lerp(&to.menu, t),
+            input: self.input.lerp(&to.input, t),
+            tooltip: self.tooltip.lerp(&to.tooltip, t),
+        }
+    }
+}
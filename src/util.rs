@@ -10,6 +10,13 @@ impl Assets {
     const ARROW_DOWN: &[u8] = include_bytes!("../assets/arrow_down.svg");
     const CHEVRON_UP: &[u8] = include_bytes!("../assets/chevron_up.svg");
     const CHEVRON_DOWN: &[u8] = include_bytes!("../assets/chevron_down.svg");
+    const CHEVRON_LEFT: &[u8] = include_bytes!("../assets/chevron_left.svg");
+    const CHEVRON_RIGHT: &[u8] = include_bytes!("../assets/chevron_right.svg");
+    const CHEVRON_UP_DOWN: &[u8] = include_bytes!("../assets/chevron_up_down.svg");
+    const CLOSE: &[u8] = include_bytes!("../assets/close.svg");
+    const WINDOW_MINIMIZE: &[u8] = include_bytes!("../assets/window_minimize.svg");
+    const WINDOW_MAXIMIZE: &[u8] = include_bytes!("../assets/window_maximize.svg");
+    const WINDOW_RESTORE: &[u8] = include_bytes!("../assets/window_restore.svg");
     const CHECKBOX_INNER: &[u8] = include_bytes!("../assets/checkbox_inner.svg");
 }
 
@@ -20,6 +27,13 @@ impl AssetSource for Assets {
             "arrow_down" => Some(Cow::Borrowed(Self::ARROW_DOWN)),
             "chevron_up" => Some(Cow::Borrowed(Self::CHEVRON_UP)),
             "chevron_down" => Some(Cow::Borrowed(Self::CHEVRON_DOWN)),
+            "chevron_left" => Some(Cow::Borrowed(Self::CHEVRON_LEFT)),
+            "chevron_right" => Some(Cow::Borrowed(Self::CHEVRON_RIGHT)),
+            "chevron_up_down" => Some(Cow::Borrowed(Self::CHEVRON_UP_DOWN)),
+            "close" => Some(Cow::Borrowed(Self::CLOSE)),
+            "window_minimize" => Some(Cow::Borrowed(Self::WINDOW_MINIMIZE)),
+            "window_maximize" => Some(Cow::Borrowed(Self::WINDOW_MAXIMIZE)),
+            "window_restore" => Some(Cow::Borrowed(Self::WINDOW_RESTORE)),
             "checkbox-inner" => Some(Cow::Borrowed(Self::CHECKBOX_INNER)),
             _ => None,
         })
@@ -46,5 +60,28 @@ pub fn border_round(cx: impl AppContext + Borrow<App>) -> Div {
     div()
         .border_1()
         .border_color(cx.theme().border)
-        .rounded_sm()
+        .rounded(cx.theme().radii.sm)
+}
+
+/// Formats the highest precedence key binding of `action`, e.g. `ctrl-shift-p`.
+/// Bindings are looked up in the key context of `context` if given, or the
+/// currently focused element otherwise.
+pub fn keybinding_text(
+    action: &dyn Action,
+    context: Option<&FocusHandle>,
+    window: &Window,
+) -> Option<SharedString> {
+    let binding = match context {
+        Some(focus) => window.highest_precedence_binding_for_action_in(action, focus),
+        None => window.highest_precedence_binding_for_action(action),
+    }?;
+    Some(
+        binding
+            .keystrokes()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" ")
+            .into(),
+    )
 }
@@ -0,0 +1,223 @@
+use std::{collections::HashMap, fs, io, path::PathBuf};
+
+use gpui::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("failed to parse window states: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("io: {0}")]
+    Io(#[from] io::Error),
+}
+
+type Result<T> = std::result::Result<T, Error>;
+
+/// Last known placement of a window.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    pub x: f32,
+    pub y: f32,
+    /// Size of the window when it is neither maximized nor fullscreen.
+    pub width: f32,
+    pub height: f32,
+    pub maximized: bool,
+    pub fullscreen: bool,
+    /// Uuid of the display the window was on, if the platform reports one.
+    pub display: Option<String>,
+}
+
+impl WindowGeometry {
+    fn of(window: &Window, cx: &App) -> Self {
+        let (bounds, maximized, fullscreen) = match window.window_bounds() {
+            WindowBounds::Windowed(bounds) => (bounds, false, false),
+            WindowBounds::Maximized(bounds) => (bounds, true, false),
+            WindowBounds::Fullscreen(bounds) => (bounds, false, true),
+        };
+        Self {
+            x: bounds.origin.x.into(),
+            y: bounds.origin.y.into(),
+            width: bounds.size.width.into(),
+            height: bounds.size.height.into(),
+            maximized,
+            fullscreen,
+            display: window
+                .display(cx)
+                .and_then(|display| display.uuid().ok())
+                .map(|uuid| uuid.to_string()),
+        }
+    }
+
+    fn bounds(&self) -> Bounds<Pixels> {
+        Bounds::new(
+            point(px(self.x), px(self.y)),
+            size(px(self.width), px(self.height)),
+        )
+    }
+}
+
+/// Geometry of named windows, saved as JSON and restored when opening them
+/// through [`WindowStates::open_window`].
+#[derive(Default)]
+pub struct WindowStates {
+    path: Option<PathBuf>,
+    windows: HashMap<String, WindowGeometry>,
+}
+
+impl Global for WindowStates {}
+
+impl WindowStates {
+    /// Loads the states saved at `path`, which is also where they get saved
+    /// to when the app quits. A missing file counts as no saved states.
+    pub fn load(path: impl Into<PathBuf>, cx: &mut App) -> Result<()> {
+        let path = path.into();
+        let windows = match fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json)?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => HashMap::new(),
+            Err(err) => return Err(err.into()),
+        };
+        cx.set_global(Self {
+            path: Some(path),
+            windows,
+        });
+        cx.on_app_quit(|cx| {
+            if let Err(err) = Self::save(cx) {
+                tracing::error!("failed to save window states: {err}");
+            }
+            async {}
+        })
+        .detach();
+        Ok(())
+    }
+
+    /// Writes the states to the file they were loaded from.
+    pub fn save(cx: &App) -> Result<()> {
+        let Some(this) = cx.try_global::<Self>() else {
+            return Ok(());
+        };
+        let Some(path) = this.path.as_ref() else {
+            return Ok(());
+        };
+        fs::write(path, serde_json::to_string_pretty(&this.windows)?)?;
+        Ok(())
+    }
+
+    pub fn get(name: &str, cx: &App) -> Option<WindowGeometry> {
+        cx.try_global::<Self>()?.windows.get(name).copied()
+    }
+
+    /// Remembers the current geometry of `window` under `name`.
+    pub fn record(name: &str, window: &Window, cx: &mut App) {
+        let geometry = WindowGeometry::of(window, cx);
+        if Self::get(name, cx) == Some(geometry) {
+            return;
+        }
+        cx.default_global::<Self>()
+            .windows
+            .insert(name.to_string(), geometry);
+    }
+
+    /// `options` with the bounds and display saved for `name`, moved onto
+    /// a connected display if the saved one is gone or the window would
+    /// end up off screen.
+    pub fn options(name: &str, mut options: WindowOptions, cx: &App) -> WindowOptions {
+        let Some(geometry) = Self::get(name, cx) else {
+            return options;
+        };
+        let display = cx
+            .displays()
+            .into_iter()
+            .find(|display| {
+                geometry.display.is_some()
+                    && display.uuid().ok().map(|uuid| uuid.to_string()) == geometry.display
+            })
+            .or_else(|| {
+                cx.displays()
+                    .into_iter()
+                    .find(|display| display.bounds().intersects(&geometry.bounds()))
+            })
+            .or_else(|| cx.primary_display());
+
+        let mut bounds = geometry.bounds();
+        if let Some(display) = display {
+            bounds = clamp(bounds, display.bounds());
+            options.display_id = Some(display.id());
+        }
+        options.window_bounds = Some(if geometry.fullscreen {
+            WindowBounds::Fullscreen(bounds)
+        } else if geometry.maximized {
+            WindowBounds::Maximized(bounds)
+        } else {
+            WindowBounds::Windowed(bounds)
+        });
+        options
+    }
+
+    /// Opens a window with its saved geometry and keeps recording it while
+    /// the window gets moved, resized, maximized or goes fullscreen.
+    pub fn open_window<V: 'static + Render>(
+        name: impl Into<SharedString>,
+        options: WindowOptions,
+        cx: &mut App,
+        build_root_view: impl FnOnce(&mut Window, &mut App) -> Entity<V>,
+    ) -> anyhow::Result<WindowHandle<V>> {
+        let name = name.into();
+        let options = Self::options(&name, options, cx);
+        cx.open_window(options, |window, cx| {
+            window
+                .observe_window_bounds(cx, move |window, cx| Self::record(&name, window, cx))
+                .detach();
+            build_root_view(window, cx)
+        })
+    }
+}
+
+/// Shrinks `bounds` to fit onto `display` and moves them fully onto it.
+fn clamp(bounds: Bounds<Pixels>, display: Bounds<Pixels>) -> Bounds<Pixels> {
+    let size = size(
+        bounds.size.width.min(display.size.width),
+        bounds.size.height.min(display.size.height),
+    );
+    let max = display.bottom_right() - point(size.width, size.height);
+    let origin = point(
+        bounds.origin.x.max(display.origin.x).min(max.x),
+        bounds.origin.y.max(display.origin.y).min(max.y),
+    );
+    Bounds::new(origin, size)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn rect(x: f32, y: f32, width: f32, height: f32) -> Bounds<Pixels> {
+        Bounds::new(point(px(x), px(y)), size(px(width), px(height)))
+    }
+
+    #[test]
+    fn clamp_fits_bounds_onto_display() {
+        let display = rect(0.0, 0.0, 1920.0, 1080.0);
+        let inside = rect(100.0, 100.0, 800.0, 600.0);
+        assert_eq!(clamp(inside, display), inside);
+        // Oversized windows shrink to the display.
+        assert_eq!(clamp(rect(50.0, 50.0, 2560.0, 1440.0), display), display);
+        // Windows left on a disconnected display move back onto this one.
+        assert_eq!(
+            clamp(rect(2400.0, -300.0, 800.0, 600.0), display),
+            rect(1120.0, 0.0, 800.0, 600.0)
+        );
+    }
+
+    #[test]
+    fn clamp_keeps_to_displays_left_of_the_primary() {
+        let display = rect(-1280.0, -200.0, 1280.0, 1024.0);
+        assert_eq!(
+            clamp(rect(-1000.0, 0.0, 640.0, 480.0), display),
+            rect(-1000.0, 0.0, 640.0, 480.0)
+        );
+        assert_eq!(
+            clamp(rect(100.0, -600.0, 640.0, 480.0), display),
+            rect(-640.0, -200.0, 640.0, 480.0)
+        );
+    }
+}
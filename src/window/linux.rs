@@ -0,0 +1,242 @@
+use super::*;
+use crate::prelude::*;
+
+/// Thickness of the invisible resize areas along the window edges.
+const RESIZE_HANDLE_SIZE: Pixels = px(6.0);
+
+/// Top bar for client-side decorations on Linux. Open the window with
+/// `WindowDecorations::Client` to use it.
+pub struct LinuxTopBar;
+
+impl LinuxTopBar {
+    fn control(
+        id: &'static str,
+        icon: &'static str,
+        hover: Hsla,
+        window: &mut Window,
+        cx: &mut Context<WindowTopBar>,
+    ) -> Stateful<Div> {
+        let interaction = Interaction::use_state(id, window, cx);
+        let bg = interaction
+            .read(cx)
+            .color(cx.theme().muted_background, hover, hover);
+        h_flex()
+            .id(id)
+            .relative()
+            .justify_center()
+            .items_center()
+            .size_6()
+            .rounded_full()
+            .bg(bg)
+            .child(Interaction::tracker(interaction))
+            // Keep clicks on the controls from moving the window.
+            .on_mouse_down(MouseButton::Left, |_, _, cx| cx.stop_propagation())
+            .child(
+                svg()
+                    .size(cx.theme().sizes.icon)
+                    .text_color(cx.theme().text)
+                    .path(icon),
+            )
+    }
+
+    fn controls(window: &mut Window, cx: &mut Context<WindowTopBar>) -> impl IntoElement {
+        let selection = cx.theme().selection;
+        let maximize_icon = if window.is_maximized() {
+            "window_restore"
+        } else {
+            "window_maximize"
+        };
+
+        h_flex()
+            .id("linux-window-controls")
+            .gap(cx.theme().spacing.md)
+            .px(cx.theme().spacing.md)
+            .items_center()
+            .child(
+                Self::control(
+                    "linux-top-bar-min-btn",
+                    "window_minimize",
+                    selection,
+                    window,
+                    cx,
+                )
+                .on_click(|_, window, _| window.minimize_window()),
+            )
+            .child(
+                Self::control(
+                    "linux-top-bar-max-btn",
+                    maximize_icon,
+                    selection,
+                    window,
+                    cx,
+                )
+                .on_click(cx.listener(|this, _, window, cx| {
+                    window.zoom_window();
+                    this.maximize_toggled(window, cx);
+                })),
+            )
+            .child(
+                Self::control(
+                    "linux-top-bar-close-btn",
+                    "close",
+                    cx.theme().error,
+                    window,
+                    cx,
+                )
+                .on_click(|_, window, _| window.remove_window()),
+            )
+    }
+
+    /// Invisible areas along the window edges and corners which start
+    /// resizing the window. Edges tiled by the compositor are left out.
+    fn resize_handles(window: &mut Window) -> Option<impl IntoElement> {
+        let Decorations::Client { tiling } = window.window_decorations() else {
+            return None;
+        };
+        if window.is_maximized() || window.is_fullscreen() {
+            return None;
+        }
+
+        let viewport = window.viewport_size();
+        let handle = RESIZE_HANDLE_SIZE;
+        let edges = [
+            (
+                ResizeEdge::Top,
+                !tiling.top,
+                CursorStyle::ResizeUpDown,
+                Bounds::new(
+                    point(handle, px(0.0)),
+                    size(viewport.width - handle * 2.0, handle),
+                ),
+            ),
+            (
+                ResizeEdge::Bottom,
+                !tiling.bottom,
+                CursorStyle::ResizeUpDown,
+                Bounds::new(
+                    point(handle, viewport.height - handle),
+                    size(viewport.width - handle * 2.0, handle),
+                ),
+            ),
+            (
+                ResizeEdge::Left,
+                !tiling.left,
+                CursorStyle::ResizeLeftRight,
+                Bounds::new(
+                    point(px(0.0), handle),
+                    size(handle, viewport.height - handle * 2.0),
+                ),
+            ),
+            (
+                ResizeEdge::Right,
+                !tiling.right,
+                CursorStyle::ResizeLeftRight,
+                Bounds::new(
+                    point(viewport.width - handle, handle),
+                    size(handle, viewport.height - handle * 2.0),
+                ),
+            ),
+            (
+                ResizeEdge::TopLeft,
+                !tiling.top && !tiling.left,
+                CursorStyle::ResizeUpLeftDownRight,
+                Bounds::new(point(px(0.0), px(0.0)), size(handle, handle)),
+            ),
+            (
+                ResizeEdge::TopRight,
+                !tiling.top && !tiling.right,
+                CursorStyle::ResizeUpRightDownLeft,
+                Bounds::new(
+                    point(viewport.width - handle, px(0.0)),
+                    size(handle, handle),
+                ),
+            ),
+            (
+                ResizeEdge::BottomLeft,
+                !tiling.bottom && !tiling.left,
+                CursorStyle::ResizeUpRightDownLeft,
+                Bounds::new(
+                    point(px(0.0), viewport.height - handle),
+                    size(handle, handle),
+                ),
+            ),
+            (
+                ResizeEdge::BottomRight,
+                !tiling.bottom && !tiling.right,
+                CursorStyle::ResizeUpLeftDownRight,
+                Bounds::new(
+                    point(viewport.width - handle, viewport.height - handle),
+                    size(handle, handle),
+                ),
+            ),
+        ];
+
+        Some(
+            deferred(
+                anchored().position(point(px(0.0), px(0.0))).child(
+                    div().relative().size(viewport).children(
+                        edges
+                            .into_iter()
+                            .enumerate()
+                            .filter(|(_, (_, enabled, _, _))| *enabled)
+                            .map(|(ix, (edge, _, cursor, bounds))| {
+                                div()
+                                    .id(("linux-resize-handle", ix))
+                                    .absolute()
+                                    .left(bounds.origin.x)
+                                    .top(bounds.origin.y)
+                                    .w(bounds.size.width)
+                                    .h(bounds.size.height)
+                                    .occlude()
+                                    .cursor(cursor)
+                                    .on_mouse_down(MouseButton::Left, move |_, window, cx| {
+                                        cx.stop_propagation();
+                                        window.start_window_resize(edge);
+                                    })
+                            }),
+                    ),
+                ),
+            )
+            .with_priority(0),
+        )
+    }
+}
+
+impl WindowTopBarImpl for LinuxTopBar {
+    fn render(
+        &self,
+        slots: TopBarSlots,
+        window: &mut Window,
+        cx: &mut Context<WindowTopBar>,
+    ) -> AnyElement {
+        h_flex()
+            .id("linux-top-bar")
+            .relative()
+            .w_full()
+            .h(cx.theme().sizes.title_bar_height)
+            .items_center()
+            .bg(cx.theme().foreground)
+            .text_color(cx.theme().text)
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(|this, event: &MouseDownEvent, window, cx| {
+                    if event.click_count == 2 {
+                        window.zoom_window();
+                        this.maximize_toggled(window, cx);
+                    } else {
+                        window.start_window_move();
+                    }
+                }),
+            )
+            .on_mouse_down(MouseButton::Right, |event, window, _| {
+                window.show_window_menu(event.position);
+            })
+            .child(slots.title)
+            .children(slots.leading)
+            .child(div().flex_1())
+            .children(slots.trailing)
+            .child(Self::controls(window, cx))
+            .children(Self::resize_handles(window))
+            .into_any()
+    }
+}
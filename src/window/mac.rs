@@ -0,0 +1,41 @@
+use super::*;
+use crate::prelude::*;
+
+/// Room left for the traffic light buttons drawn by the system.
+const TRAFFIC_LIGHTS_WIDTH: Pixels = px(78.0);
+
+/// Top bar for windows with a transparent titlebar on macOS, which keep the
+/// native window controls.
+pub struct MacTopBar;
+
+impl WindowTopBarImpl for MacTopBar {
+    fn render(
+        &self,
+        slots: TopBarSlots,
+        window: &mut Window,
+        cx: &mut Context<WindowTopBar>,
+    ) -> AnyElement {
+        h_flex()
+            .id("mac-top-bar")
+            .relative()
+            .w_full()
+            .h(cx.theme().sizes.title_bar_height)
+            .when(!window.is_fullscreen(), |this| {
+                this.pl(TRAFFIC_LIGHTS_WIDTH)
+            })
+            .items_center()
+            .text_color(cx.theme().text)
+            .window_control_area(WindowControlArea::Drag)
+            .on_click(cx.listener(|this, event: &ClickEvent, window, cx| {
+                if event.click_count() == 2 {
+                    window.titlebar_double_click();
+                    this.maximize_toggled(window, cx);
+                }
+            }))
+            .child(slots.title)
+            .children(slots.leading)
+            .child(div().flex_1())
+            .children(slots.trailing)
+            .into_any()
+    }
+}
@@ -1,3 +1,4 @@
+pub mod geometry;
 pub mod linux;
 pub mod mac;
 pub mod windows;
@@ -5,27 +6,198 @@ pub mod windows;
 use crate::prelude::*;
 use gpui::{prelude::*, *};
 
+type SlotBuilder = Box<dyn Fn(&mut Window, &mut App) -> AnyElement>;
+
+/// Content of a [`WindowTopBar`] placed around the window controls.
+struct TopBarSlots {
+    /// Absolutely positioned across the whole bar, so it stays centered
+    /// regardless of the other slots.
+    title: AnyElement,
+    leading: Option<AnyElement>,
+    trailing: Option<AnyElement>,
+}
+
 trait WindowTopBarImpl {
-    fn render(&self, window: &mut Window, cx: &mut Context<WindowTopBar>) -> AnyElement;
+    fn render(
+        &self,
+        slots: TopBarSlots,
+        window: &mut Window,
+        cx: &mut Context<WindowTopBar>,
+    ) -> AnyElement;
 }
 
-pub struct WindowTopBar(Box<dyn WindowTopBarImpl>);
+/// Look of the window controls drawn by a [`WindowTopBar`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TopBarStyle {
+    Windows,
+    Linux,
+    Mac,
+}
+
+impl TopBarStyle {
+    /// The style of the platform this was compiled for.
+    pub fn current() -> Self {
+        if cfg!(target_os = "windows") {
+            Self::Windows
+        } else if cfg!(target_os = "macos") {
+            Self::Mac
+        } else {
+            Self::Linux
+        }
+    }
+}
+
+/// Title bar of a window drawing its own decorations.
+///
+/// Besides the window controls it has a leading slot, e.g. for an app icon
+/// and a menu bar, a centered title and a trailing slot for custom actions
+/// in front of the controls. The free space in between moves the window.
+pub struct WindowTopBar {
+    inner: Box<dyn WindowTopBarImpl>,
+    leading: Option<SlotBuilder>,
+    title: Option<SharedString>,
+    trailing: Option<SlotBuilder>,
+    persist: Option<SharedString>,
+    _pending_geometry: Option<Subscription>,
+}
 
 impl WindowTopBar {
     pub fn new<C: AppContext>(cx: &mut C) -> C::Result<Entity<Self>> {
-        cx.new(|_| {
-            let window = windows::WindowsTopBar {};
-            WindowTopBar(Box::new(window))
-        })
+        Self::with_style(TopBarStyle::current(), cx)
+    }
+
+    pub fn with_style<C: AppContext>(style: TopBarStyle, cx: &mut C) -> C::Result<Entity<Self>> {
+        cx.new(|_| Self::from_style(style))
+    }
+
+    /// A top bar to be configured with the slot builders before putting it
+    /// into an entity.
+    pub fn from_style(style: TopBarStyle) -> Self {
+        let inner: Box<dyn WindowTopBarImpl> = match style {
+            TopBarStyle::Windows => Box::new(windows::WindowsTopBar),
+            TopBarStyle::Linux => Box::new(linux::LinuxTopBar),
+            TopBarStyle::Mac => Box::new(mac::MacTopBar),
+        };
+        Self {
+            inner,
+            leading: None,
+            title: None,
+            trailing: None,
+            persist: None,
+            _pending_geometry: None,
+        }
+    }
+
+    pub fn leading<E: IntoElement>(
+        self,
+        builder: impl Fn(&mut Window, &mut App) -> E + 'static,
+    ) -> Self {
+        Self {
+            leading: Some(Box::new(move |window, cx| {
+                builder(window, cx).into_any_element()
+            })),
+            ..self
+        }
+    }
+
+    /// Shows `title` instead of the title of the window.
+    pub fn title(self, title: impl Into<SharedString>) -> Self {
+        Self {
+            title: Some(title.into()),
+            ..self
+        }
+    }
+
+    pub fn trailing<E: IntoElement>(
+        self,
+        builder: impl Fn(&mut Window, &mut App) -> E + 'static,
+    ) -> Self {
+        Self {
+            trailing: Some(Box::new(move |window, cx| {
+                builder(window, cx).into_any_element()
+            })),
+            ..self
+        }
+    }
+
+    /// Records the geometry of the window under `name` in the
+    /// [`WindowStates`](geometry::WindowStates) when it gets maximized or
+    /// restored through the bar. The caption buttons on Windows are handled
+    /// by the OS, open the window with
+    /// [`WindowStates::open_window`](geometry::WindowStates::open_window) to
+    /// track those.
+    pub fn persist(self, name: impl Into<SharedString>) -> Self {
+        Self {
+            persist: Some(name.into()),
+            ..self
+        }
+    }
+
+    /// Sets the title of the window, which the top bar follows unless it
+    /// got its own title.
+    pub fn set_window_title(&mut self, title: &str, window: &mut Window, cx: &mut Context<Self>) {
+        window.set_window_title(title);
+        cx.notify();
+    }
+
+    /// Called by the controls after toggling maximize, records the geometry
+    /// once the window has its new bounds.
+    fn maximize_toggled(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.persist.is_none() {
+            return;
+        }
+        self._pending_geometry = Some(cx.observe_window_bounds(window, |this, window, cx| {
+            if let Some(name) = this.persist.as_ref() {
+                geometry::WindowStates::record(name, window, cx);
+            }
+            this._pending_geometry = None;
+        }));
     }
 
-    pub fn top() -> AnyElement {
-        div().into_any_element()
+    /// Wraps slot content so it keeps the pointer to itself, instead of the
+    /// drag area of the bar behind it taking over its clicks.
+    fn slot(id: &'static str, content: AnyElement) -> AnyElement {
+        h_flex()
+            .id(id)
+            .h_full()
+            .flex_none()
+            .items_center()
+            .occlude()
+            .child(content)
+            .into_any_element()
     }
 }
 
 impl Render for WindowTopBar {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        self.0.render(window, cx)
+        let title = self
+            .title
+            .clone()
+            .unwrap_or_else(|| window.window_title().into());
+        let slots = TopBarSlots {
+            title: h_flex()
+                .absolute()
+                .inset_0()
+                .justify_center()
+                .items_center()
+                .child(
+                    div()
+                        .max_w_1_2()
+                        .overflow_hidden()
+                        .text_ellipsis()
+                        .whitespace_nowrap()
+                        .child(title),
+                )
+                .into_any_element(),
+            leading: self
+                .leading
+                .as_ref()
+                .map(|builder| Self::slot("top-bar-leading", builder(window, cx))),
+            trailing: self
+                .trailing
+                .as_ref()
+                .map(|builder| Self::slot("top-bar-trailing", builder(window, cx))),
+        };
+        self.inner.render(slots, window, cx)
     }
 }
@@ -4,15 +4,13 @@ use crate::prelude::*;
 pub struct WindowsTopBar;
 
 impl WindowsTopBar {
-    fn top_resize(window: &mut Window, _: &mut Context<WindowTopBar>) -> impl IntoElement {
+    fn top_resize(window: &mut Window, cx: &mut Context<WindowTopBar>) -> impl IntoElement {
         div()
             .h(px(1.))
             .w_full()
-            .when(window.is_window_active(), |this| {
-                this.bg(gpui::rgb(0xfb94bc))
-            })
+            .when(window.is_window_active(), |this| this.bg(cx.theme().accent))
             .when(!window.is_window_active(), |this| {
-                this.bg(gpui::rgb(0x3d3437))
+                this.bg(cx.theme().border)
             })
             .cursor_n_resize()
             .on_mouse_down(MouseButton::Left, |_, window, _| {
@@ -20,9 +18,19 @@ impl WindowsTopBar {
             })
     }
 
-    fn close(_: &mut Window, _: &mut Context<WindowTopBar>) -> impl IntoElement {
+    fn control(
+        id: &'static str,
+        hover: Hsla,
+        window: &mut Window,
+        cx: &mut Context<WindowTopBar>,
+    ) -> Stateful<Div> {
+        let interaction = Interaction::use_state(id, window, cx);
+        let bg = interaction
+            .read(cx)
+            .color(transparent_black(), hover, hover);
         h_flex()
-            .id("windows-top-bar-close-btn")
+            .id(id)
+            .relative()
             .justify_center()
             .items_center()
             .content_center()
@@ -30,32 +38,28 @@ impl WindowsTopBar {
             .w(px(36.))
             .h_full()
             .text_size(px(10.0))
-            .hover(|style| {
-                style.bg(gpui::Rgba {
-                    r: 232.0 / 255.0,
-                    g: 17.0 / 255.0,
-                    b: 32.0 / 255.0,
-                    a: 1.0,
-                })
-            })
+            .bg(bg)
+            .child(Interaction::tracker(interaction))
+    }
+
+    fn close(window: &mut Window, cx: &mut Context<WindowTopBar>) -> impl IntoElement {
+        let hover = gpui::Rgba {
+            r: 232.0 / 255.0,
+            g: 17.0 / 255.0,
+            b: 32.0 / 255.0,
+            a: 1.0,
+        };
+        Self::control("windows-top-bar-close-btn", hover.into(), window, cx)
             .window_control_area(WindowControlArea::Close)
             .child("\u{e8bb}")
     }
 
     fn max(window: &mut Window, cx: &mut Context<WindowTopBar>) -> impl IntoElement {
-        h_flex()
-            .id("windows-top-bar-max-btn")
-            .justify_center()
-            .items_center()
-            .content_center()
-            .occlude()
-            .w(px(36.))
-            .h_full()
-            .text_size(px(10.0))
-            .hover(|style| style.bg(cx.theme().selection))
+        let maximized = window.is_maximized();
+        Self::control("windows-top-bar-max-btn", cx.theme().selection, window, cx)
             .window_control_area(WindowControlArea::Max)
             .map(|this| {
-                if window.is_maximized() {
+                if maximized {
                     this.child("\u{e923}")
                 } else {
                     this.child("\u{e922}")
@@ -63,17 +67,8 @@ impl WindowsTopBar {
             })
     }
 
-    fn min(_: &mut Window, cx: &mut Context<WindowTopBar>) -> impl IntoElement {
-        h_flex()
-            .id("windows-top-bar-min-btn")
-            .justify_center()
-            .items_center()
-            .content_center()
-            .occlude()
-            .w(px(36.))
-            .h_full()
-            .text_size(px(10.0))
-            .hover(|style| style.bg(cx.theme().selection))
+    fn min(window: &mut Window, cx: &mut Context<WindowTopBar>) -> impl IntoElement {
+        Self::control("windows-top-bar-min-btn", cx.theme().selection, window, cx)
             .window_control_area(WindowControlArea::Min)
             .child("\u{e921}")
     }
@@ -94,21 +89,29 @@ impl WindowsTopBar {
 }
 
 impl WindowTopBarImpl for WindowsTopBar {
-    fn render(&self, window: &mut Window, cx: &mut Context<WindowTopBar>) -> AnyElement {
+    fn render(
+        &self,
+        slots: TopBarSlots,
+        window: &mut Window,
+        cx: &mut Context<WindowTopBar>,
+    ) -> AnyElement {
         v_flex()
             .when(!window.is_maximized() && !window.is_fullscreen(), |this| {
                 this.child(Self::top_resize(window, cx))
             })
             .child(
                 h_flex()
+                    .relative()
                     .w_full()
                     .content_stretch()
-                    .h_8()
+                    .h(cx.theme().sizes.title_bar_height)
                     .window_control_area(WindowControlArea::Drag)
-                    .justify_between()
                     .items_center()
                     .text_color(cx.theme().text)
-                    .child(div())
+                    .child(slots.title)
+                    .children(slots.leading)
+                    .child(div().flex_1())
+                    .children(slots.trailing)
                     .child(Self::controls(window, cx)),
             )
             .into_any()